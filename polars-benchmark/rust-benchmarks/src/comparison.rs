@@ -0,0 +1,147 @@
+//! Compares a Rust run against a Python results file for `--compare`.
+
+use crate::{BenchmarkResult, BenchmarkSuite};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// One operation of `comparison_report.json`. The side that did not run the
+/// operation is `null`, and so are the ratios that need it.
+#[derive(Serialize, Debug)]
+pub struct ComparisonEntry {
+    pub operation: String,
+    pub rust_ms: Option<f64>,
+    pub python_ms: Option<f64>,
+    /// `python_ms / rust_ms`, so above 1 means Rust was faster
+    pub speedup_factor: Option<f64>,
+    /// Python `memory_mb` over Rust `memory_mb`, so above 1 means Rust used less
+    pub memory_ratio: Option<f64>,
+    /// Set when both sides stored an `output_checksum` and they differ, meaning the
+    /// two suites did not compute the same result
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub checksum_mismatch: bool,
+}
+
+/// Results comparable across the suites: not skipped, not timed out and not the
+/// `--streaming` rerun, which the Python suite has no counterpart for.
+fn comparable(suite: &BenchmarkSuite) -> impl Iterator<Item = &BenchmarkResult> {
+    suite
+        .results
+        .iter()
+        .filter(|result| !result.streaming && !result.timed_out && result.skipped_reason.is_none())
+}
+
+fn ratio(numerator: f64, denominator: f64) -> Option<f64> {
+    (denominator > 0.0).then(|| numerator / denominator)
+}
+
+/// Matches the results of both suites by operation name, Rust operations first in
+/// run order, then those only Python ran.
+pub fn compare_suites(rust: &BenchmarkSuite, python: &BenchmarkSuite) -> Vec<ComparisonEntry> {
+    let python_results: HashMap<&str, &BenchmarkResult> =
+        comparable(python).map(|result| (result.operation.as_str(), result)).collect();
+    let mut entries: Vec<ComparisonEntry> = comparable(rust)
+        .map(|rust_result| {
+            let python_result = python_results.get(rust_result.operation.as_str());
+            let rust_ms = rust_result.precise_duration_ms();
+            let python_ms = python_result.map(|result| result.precise_duration_ms());
+            ComparisonEntry {
+                operation: rust_result.operation.clone(),
+                rust_ms: Some(rust_ms),
+                python_ms,
+                speedup_factor: python_ms.and_then(|python_ms| ratio(python_ms, rust_ms)),
+                memory_ratio: python_result
+                    .and_then(|result| ratio(result.memory_mb as f64, rust_result.memory_mb as f64)),
+                checksum_mismatch: matches!(
+                    (rust_result.output_checksum, python_result.and_then(|result| result.output_checksum)),
+                    (Some(rust), Some(python)) if rust != python
+                ),
+            }
+        })
+        .collect();
+    
+    for python_result in comparable(python) {
+        if !entries.iter().any(|entry| entry.operation == python_result.operation) {
+            entries.push(ComparisonEntry {
+                operation: python_result.operation.clone(),
+                rust_ms: None,
+                python_ms: Some(python_result.precise_duration_ms()),
+                speedup_factor: None,
+                memory_ratio: None,
+                checksum_mismatch: false,
+            });
+        }
+    }
+    entries
+}
+
+/// One line naming the faster suite and its factor for every operation both ran,
+/// e.g. `Rust is 3.2× faster on group_by, 1.1× faster on filter`.
+pub fn summary_line(entries: &[ComparisonEntry]) -> String {
+    let mut rust_faster = Vec::new();
+    let mut python_faster = Vec::new();
+    for entry in entries {
+        match entry.speedup_factor {
+            Some(factor) if factor >= 1.0 => rust_faster.push(format!("{:.1}× faster on {}", factor, entry.operation)),
+            Some(factor) if factor > 0.0 => {
+                python_faster.push(format!("{:.1}× faster on {}", 1.0 / factor, entry.operation))
+            }
+            _ => {}
+        }
+    }
+    
+    let mut parts = Vec::new();
+    if !rust_faster.is_empty() {
+        parts.push(format!("Rust is {}", rust_faster.join(", ")));
+    }
+    if !python_faster.is_empty() {
+        parts.push(format!("Python is {}", python_faster.join(", ")));
+    }
+    if parts.is_empty() {
+        "No operation was timed by both suites".to_string()
+    } else {
+        parts.join("; ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn suite(results: serde_json::Value) -> BenchmarkSuite {
+        serde_json::from_value(serde_json::json!({
+            "timestamp": "",
+            "results": results,
+            "system_info": {"os": "", "cpu_count": 1, "total_memory_gb": 1},
+            "dataset_info": {"source": "", "rows_limit": null},
+        }))
+        .unwrap()
+    }
+    
+    #[test]
+    fn compare_suites_matches_by_operation_and_keeps_one_sided_entries() {
+        let rust = suite(serde_json::json!([
+            {"operation": "group_by", "duration_ms": 10, "duration_us": 10_000, "memory_mb": 5, "output_checksum": 1},
+            {"operation": "sort", "duration_ms": 40, "memory_mb": 0},
+            {"operation": "sort", "duration_ms": 1, "memory_mb": 0, "streaming": true},
+            {"operation": "rust_only", "duration_ms": 1, "memory_mb": 0},
+        ]));
+        let python = suite(serde_json::json!([
+            {"operation": "group_by", "duration_ms": 32, "memory_mb": 10, "output_checksum": 2},
+            {"operation": "sort", "duration_ms": 20, "memory_mb": 3},
+            {"operation": "python_only", "duration_ms": 7, "memory_mb": 1},
+        ]));
+        
+        let entries = compare_suites(&rust, &python);
+        let operations: Vec<_> = entries.iter().map(|entry| entry.operation.as_str()).collect();
+        assert_eq!(operations, ["group_by", "sort", "rust_only", "python_only"]);
+        assert_eq!(entries[0].speedup_factor, Some(3.2));
+        assert_eq!(entries[0].memory_ratio, Some(2.0));
+        assert!(entries[0].checksum_mismatch);
+        assert_eq!(entries[1].speedup_factor, Some(0.5));
+        assert_eq!(entries[1].memory_ratio, None);
+        assert_eq!((entries[2].python_ms, entries[2].speedup_factor), (None, None));
+        assert_eq!((entries[3].rust_ms, entries[3].python_ms), (None, Some(7.0)));
+        
+        assert_eq!(summary_line(&entries), "Rust is 3.2× faster on group_by; Python is 2.0× faster on sort");
+    }
+}
\ No newline at end of file
@@ -1,12 +1,98 @@
 use clap::Parser;
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::time::Instant;
-use sysinfo::{System, SystemExt};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessExt, ProcessRefreshKind, RefreshKind, System, SystemExt};
+
+mod comparison;
+mod output;
+mod regression;
+
+use output::ResultsFormatter;
 
 const DEFAULT_S3_DATASET: &str = "s3://coiled-datasets/timeseries/20-years/parquet";
 
+/// File format of the dataset at `data_path`.
+#[derive(clap::ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum InputFormat {
+    #[default]
+    Parquet,
+    Csv,
+    Ipc,
+}
+
+impl InputFormat {
+    fn name(self) -> &'static str {
+        match self {
+            InputFormat::Parquet => "parquet",
+            InputFormat::Csv => "csv",
+            InputFormat::Ipc => "ipc",
+        }
+    }
+}
+
+/// File format of the results written to `--output`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum OutputFormat {
+    #[default]
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl OutputFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Markdown => "md",
+        }
+    }
+}
+
+/// The benchmarks both the Rust and the Python suite run, for `--select`.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[value(rename_all = "snake_case")]
+enum BenchmarkKind {
+    Read,
+    Filter,
+    Aggregation,
+    GroupBy,
+    Sort,
+    ComplexQuery,
+}
+
+impl BenchmarkKind {
+    /// Name the benchmark is registered under in the `BenchmarkRegistry`.
+    fn name(self) -> &'static str {
+        match self {
+            BenchmarkKind::Read => "read",
+            BenchmarkKind::Filter => "filter",
+            BenchmarkKind::Aggregation => "aggregation",
+            BenchmarkKind::GroupBy => "group_by",
+            BenchmarkKind::Sort => "sort",
+            BenchmarkKind::ComplexQuery => "complex_query",
+        }
+    }
+    
+    /// Operation name the benchmark records its result under, which for `read`
+    /// includes the dataset format.
+    fn operation(self, format: InputFormat) -> String {
+        match self {
+            BenchmarkKind::Read => format!("read_{}", format.name()),
+            kind => kind.name().to_string(),
+        }
+    }
+}
+
 #[derive(Parser)]
 #[command(name = "polars-rust-benchmark")]
 #[command(about = "Rust benchmarks for Polars using Coiled timeseries dataset")]
@@ -14,25 +100,606 @@ struct Args {
     #[arg(default_value = DEFAULT_S3_DATASET)]
     data_path: String,
     
-    #[arg(short, long, default_value = "../results/rust_results.json")]
+    // A later `--output` replaces an earlier one, which `run_with_timeouts` relies on
+    // to redirect the child processes it starts with this run's arguments.
+    #[arg(short, long, default_value = "../results/rust_results.json", overrides_with = "output")]
     output: PathBuf,
     
+    /// Format of the results file. CSV and Markdown hold one row per result and
+    /// cannot be read back by `--regression-suite` or `--compare`
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    output_format: OutputFormat,
+    
     #[arg(long)]
     limit_rows: Option<usize>,
+    
+    /// Format of the dataset at `data_path`
+    #[arg(long, value_enum, default_value_t = InputFormat::Parquet)]
+    format: InputFormat,
+    
+    /// Field separator for `--format csv`; must be a single ASCII character
+    #[arg(long, default_value_t = ',')]
+    delimiter: char,
+    
+    /// Number of complex queries to run concurrently in the parallel collect benchmark
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..))]
+    parallel_queries: u64,
+    
+    /// Rebuild the `name` column with strings of this average length (in bytes) and
+    /// rerun the filter and group by benchmarks against it
+    #[arg(long, value_name = "AVG_LEN")]
+    generate_long_strings: Option<usize>,
+    
+    /// Store a checksum of every collected benchmark output so repeated Rust runs can be checked for correctness
+    #[arg(long)]
+    verify_output: bool,
+    
+    /// Cap on the rows collected into memory by benchmarks that need the whole frame
+    /// up front (partitioned write, take vs filter)
+    #[arg(long, default_value_t = 10_000_000)]
+    max_eager_rows: usize,
+    
+    /// Cap on the rows a cross join may produce; larger joins are skipped
+    #[arg(long, default_value_t = 100_000_000)]
+    max_output_rows: usize,
+    
+    /// Add a synthetic `json_str` column and benchmark parsing it with `json_extract`
+    #[arg(long)]
+    generate_json_column: bool,
+    
+    /// Local copy of the dataset; downloaded on first use, then read alongside the
+    /// remote path to compare the two
+    #[arg(long, value_name = "PATH")]
+    local_cache_path: Option<PathBuf>,
+    
+    /// Comma-separated chunk sizes (in rows) for the into-chunks benchmark
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "10000,100000,1000000",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    chunk_sizes: Vec<u64>,
+    
+    /// Comma-separated names of the benchmarks to run; every registered benchmark
+    /// runs when omitted
+    #[arg(long, value_delimiter = ',', value_name = "NAME")]
+    benchmark: Vec<String>,
+    
+    /// Only run these of the benchmarks shared with the Python suite, recording the
+    /// others as skipped
+    #[arg(long, value_enum, num_args = 1.., value_delimiter = ',', conflicts_with = "benchmark")]
+    select: Vec<BenchmarkKind>,
+    
+    /// Run the benchmarks at 1%, 10% and 100% of the rows (or of `--limit-rows`) and
+    /// record how each operation's time scales with input size
+    #[arg(long)]
+    scaling_test: bool,
+    
+    /// Disassemble this binary with `objdump` to check whether the zip kernel was
+    /// vectorized
+    #[arg(long)]
+    check_asm: bool,
+    
+    /// Also run the sort benchmark on the streaming engine with spilling to disk forced
+    #[arg(long)]
+    force_external_sort: bool,
+    
+    /// Run every benchmark a second time on the streaming engine, with the results
+    /// marked `streaming: true`
+    #[arg(long)]
+    streaming: bool,
+    
+    /// Compare this run against a baseline results JSON, write `regression_report.json`
+    /// next to `--output` and exit non-zero if any operation regressed
+    #[arg(long, value_name = "BASELINE_JSON")]
+    regression_suite: Option<PathBuf>,
+    
+    /// Relative slowdown over the baseline that counts as a regression, 0.1 being 10%
+    #[arg(long, default_value_t = 0.1)]
+    regression_threshold: f64,
+    
+    /// Run every benchmark in a child process and give up on it after this many
+    /// seconds, recording it as `timed_out`
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..), conflicts_with = "thread_scaling_test")]
+    timeout_secs: Option<u64>,
+    
+    /// Set by `run_child_suite` and `run_with_timeouts` on their child processes:
+    /// run only these comma-separated benchmarks, every registered one when empty,
+    /// without the modes that start child processes themselves
+    #[arg(long, hide = true, value_name = "NAMES")]
+    child_benchmarks: Option<String>,
+    
+    /// Compare this run against a Python results JSON and write
+    /// `comparison_report.json` next to `--output`
+    #[arg(long, value_name = "PYTHON_RESULTS_JSON")]
+    compare: Option<PathBuf>,
+    
+    /// Rerun the selected benchmarks at every `--thread-counts` setting and at the
+    /// default pool size, recording each operation's parallel efficiency
+    #[arg(long)]
+    thread_scaling_test: bool,
+    
+    /// Run the benchmarks this many times after one discarded warmup run and report
+    /// the mean duration and its standard deviation. Ignored for S3 paths, where
+    /// repeated reads would measure the network rather than Polars
+    #[arg(long, value_parser = clap::value_parser!(u32).range(2..))]
+    iterations: Option<u32>,
+    
+    /// Run each benchmark this many times, discarding the results, right before the
+    /// run that is timed
+    #[arg(long, default_value_t = 0)]
+    warmup: u32,
+    
+    /// Comma-separated `POLARS_MAX_THREADS` values for `--thread-scaling-test`
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "1,2,4,8",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    thread_counts: Vec<u64>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Settings from the command line that every benchmark function needs.
+#[derive(Clone, Copy, Debug)]
+struct BenchmarkOptions {
+    verify_output: bool,
+    max_eager_rows: usize,
+    /// Rows scanned from the dataset, `None` for all of them
+    row_limit: Option<usize>,
+    /// Also run every benchmark with `with_streaming(true)`, for `--streaming`
+    streaming: bool,
+    /// Untimed runs of each benchmark before its timed run, for `--warmup`
+    warmup_runs: u32,
+}
+
+impl BenchmarkOptions {
+    fn new(args: &Args, row_limit: Option<usize>) -> Self {
+        BenchmarkOptions {
+            verify_output: args.verify_output,
+            // A `limit` on the lazy frame replaces the scan's `n_rows`, so fold the row
+            // limit into the cap rather than relying on the scan to apply it.
+            max_eager_rows: row_limit.map_or(args.max_eager_rows, |limit| limit.min(args.max_eager_rows)),
+            row_limit,
+            streaming: args.streaming,
+            warmup_runs: args.warmup,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 struct BenchmarkResult {
     operation: String,
-    duration_ms: u64,
+    /// `null` for skipped results, which have no timing
+    duration_ms: Option<u64>,
+    /// Same measurement as `duration_ms` at microsecond resolution, for operations
+    /// that finish in well under a millisecond
+    #[serde(default)]
+    duration_us: u64,
     memory_mb: u64,
+    /// Highest resident set size of this process seen by `PeakMemorySampler` while the
+    /// operation ran, or while its whole benchmark function ran for operations that do
+    /// not sample themselves
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    peak_memory_mb: Option<u64>,
     rows_processed: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parallelism_efficiency: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    n_chunks_input: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    num_partitions: Option<usize>,
+    /// How many times faster this variant ran than the baseline it is compared against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    speedup_ratio: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_list_elements: Option<usize>,
+    /// Rows that came out null after parsing their JSON payload or datetime string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parse_failures: Option<usize>,
+    /// Time of a UDF divided by the time of the native kernel doing the same work
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    udf_overhead_ratio: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    num_bins: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output_dtype: Option<String>,
+    /// Fraction of rows whose value was changed by clamping
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    clamped_fraction: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rows_per_second: Option<f64>,
+    /// Sum of the list lengths produced by splitting strings into tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_tokens: Option<usize>,
+    /// Number of nodes in the benchmarked expression tree
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expr_nodes: Option<usize>,
+    /// How many times faster the local cache was read than the remote dataset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    remote_vs_local_speedup: Option<f64>,
+    /// Boolean answer of the benchmarked operation, for checking correctness
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result_bool: Option<bool>,
+    /// Whether the extended Series had spare capacity reserved for the new values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    capacity_pre_allocated: Option<bool>,
+    /// Columns whose name or dtype did not match the expected schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema_mismatch_count: Option<usize>,
+    /// Whether the input had nulls, so null-aware code paths were exercised
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nulls_present: Option<bool>,
+    /// Columns whose inferred dtype differs from the Parquet schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    inferred_schema_mismatches: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_nulls_found: Option<usize>,
+    /// Time to build a struct and read a field back out, divided by the time to read
+    /// the same column directly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    round_trip_overhead: Option<f64>,
+    /// Rows in the output, when that differs from the rows processed (e.g. tied modes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result_rows: Option<usize>,
+    /// Scalar answer of the benchmarked operation, for checking correctness
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result_value: Option<String>,
+    /// Row groups whose min/max statistics could match the predicate, out of those in the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    estimated_row_groups_scanned: Option<usize>,
+    /// Whether the input strings were mostly multi-byte UTF-8 rather than ASCII
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_unicode_heavy: Option<bool>,
+    /// Distance of the approximate distinct count from the exact one, as a percentage of the exact count
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    approx_error_pct: Option<f64>,
+    /// Distinct values of the struct column used as the group by key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unique_struct_keys: Option<usize>,
+    /// Size of the Polars thread pool the benchmark ran on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    thread_count: Option<usize>,
+    /// Distinct values in the synthetic group by key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cardinality: Option<usize>,
+    /// Order of the input before sorting: `random`, `nearly_sorted` or `reverse_sorted`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sort_input_distribution: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    num_row_groups: Option<usize>,
+    /// Row count stored in the Parquet footer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_rows_in_metadata: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file_size_bytes: Option<u64>,
+    /// Rows in each rolling window
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    window_size: Option<usize>,
+    /// Slope of log duration against log input size, across the `--scaling-test` runs
+    /// or across the sizes a benchmark tries itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scaling_exponent: Option<f64>,
+    /// Whether `scaling_exponent` is above 1.2
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    superlinear: Option<bool>,
+    /// Number of train/test pairs produced by a k-fold split
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    folds_generated: Option<usize>,
+    /// Rows across all test folds, which equals the input rows when every row is
+    /// held out exactly once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_rows_split: Option<usize>,
+    /// Rows in the output, for operations whose output height differs from the input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output_row_count: Option<usize>,
+    /// `output_row_count` divided by `rows_processed`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expansion_ratio: Option<f64>,
+    /// Levels of struct nesting between the column and the field that was read
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nesting_depth: Option<usize>,
+    /// Parquet files the dataset was read from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    num_files: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    avg_file_size_bytes: Option<u64>,
+    /// Whether the disassembled zip kernel contains packed SIMD select instructions,
+    /// only with `--check-asm`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    zip_vectorized: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    columns_in_frame: Option<usize>,
+    /// Size of the input Arrow arrays that crossed into Polars
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bytes_transferred: Option<u64>,
+    /// Whether the operation took at least half as long as collecting the whole scan,
+    /// meaning it most likely read every row rather than stopping early
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    full_scan: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    columns_cast: Option<usize>,
+    /// `estimated_size` of the input frame
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    input_size_bytes: Option<u64>,
+    /// `estimated_size` of the output frame
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output_size_bytes: Option<u64>,
+    /// Whether the streaming engine wrote intermediate state to disk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    spill_occurred: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    spill_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    columns_with_overridden_dtype: Option<usize>,
+    /// Fraction of the input rows dropped as duplicates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    duplicates_removed_fraction: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    csv_separator: Option<char>,
+    /// CSV quote style: `necessary` (the default) or `always`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    csv_quoting: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    peak_memory_collect_mb: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    peak_memory_sink_mb: Option<u64>,
+    /// Sum of the list lengths returned by `extract_all`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total_matches_found: Option<usize>,
+    /// Columns whose scanned dtype differed from the expected schema and were cast back
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema_cast_columns: Option<usize>,
+    /// `estimated_size` of the frame the operation was applied to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    frame_memory_bytes: Option<u64>,
+    /// Whether the sort was asked to keep equal keys in their input order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    is_stable: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    num_record_batches: Option<usize>,
+    /// Serialized size of what a writer produced in memory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output_bytes: Option<u64>,
+    /// Share of a group by's time spent computing the group indices, before any aggregation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index_time_fraction: Option<f64>,
+    /// `estimated_size` of the frame the operation produced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    estimated_size_bytes: Option<u64>,
+    /// Growth of this process's peak resident set size over its size before the operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rss_delta_bytes: Option<u64>,
+    /// `|estimated_size_bytes - rss_delta_bytes| / rss_delta_bytes * 100`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    estimate_error_pct: Option<f64>,
+    /// Distinct keys the group by produced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    num_groups: Option<usize>,
+    /// Fraction of rows a predicate kept
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    match_rate: Option<f64>,
+    /// Time in microseconds spent in each node of the physical plan, from `LazyFrame::profile`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    node_timings: Option<Vec<(String, u64)>>,
+    /// Encoded min/max bytes of the column statistics in a Parquet footer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    statistics_size_bytes: Option<u64>,
+    /// Duration divided by the number of groups, in microseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    overhead_per_group_us: Option<f64>,
+    /// Files in the directory that the scan's glob pattern matches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    files_matched_by_glob: Option<usize>,
+    /// How much longer the glob scan took than reading the same rows from one file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file_listing_overhead_ms: Option<f64>,
+    /// Rows per row group the Parquet file was written with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    row_group_size: Option<usize>,
+    /// Chunks in the frame the operation produced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    n_chunks_output: Option<usize>,
+    /// Sample standard deviation of the duration across `--iterations` runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    duration_stddev_ms: Option<f64>,
+    /// Timed runs the duration was averaged over, with `--iterations`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    iterations: Option<u32>,
+    /// Whether the operation ran on the streaming engine, with `--streaming`
+    #[serde(default)]
+    streaming: bool,
+    /// Why the operation has no timing, when the streaming engine rejected it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    skipped_reason: Option<String>,
+    /// Whether the benchmark was abandoned after `--timeout-secs`, in which case
+    /// `duration_ms` is the timeout
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    timed_out: bool,
+    /// Seeded Polars row hash of the output, see `output_checksum`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output_checksum: Option<u64>,
+}
+
+impl BenchmarkResult {
+    fn new(operation: &str, duration: Duration, memory_mb: u64, rows_processed: Option<usize>) -> Self {
+        BenchmarkResult {
+            operation: operation.to_string(),
+            duration_ms: Some(duration.as_millis() as u64),
+            duration_us: duration.as_micros() as u64,
+            memory_mb,
+            rows_processed,
+            ..Default::default()
+        }
+    }
+    
+    /// A result for `operation` that did not run, with no duration.
+    fn skipped(operation: &str, reason: String) -> Self {
+        BenchmarkResult {
+            operation: operation.to_string(),
+            skipped_reason: Some(reason),
+            ..Default::default()
+        }
+    }
+    
+    /// Records `df`, the frame the operation produced: its `estimated_size` as
+    /// `output_size_bytes` and `rows_processed` over the duration as
+    /// `rows_per_second`, unless the benchmark set those itself, and a checksum when
+    /// `--verify-output` is set.
+    fn with_output(mut self, df: &DataFrame, opts: &BenchmarkOptions) -> PolarsResult<Self> {
+        self.output_size_bytes = self.output_size_bytes.or(Some(df.estimated_size() as u64));
+        if let (None, Some(rows)) = (self.rows_per_second, self.rows_processed) {
+            self.rows_per_second = rows_per_second(rows, Duration::from_micros(self.duration_us));
+        }
+        if opts.verify_output {
+            self.output_checksum = Some(output_checksum(df)?);
+        }
+        Ok(self)
+    }
+    
+    /// Duration in milliseconds, from `duration_us` when the result has it. Results
+    /// written before `duration_us` existed, and the Python suite's, only have whole
+    /// milliseconds.
+    fn precise_duration_ms(&self) -> f64 {
+        if self.duration_us > 0 {
+            self.duration_us as f64 / 1_000.0
+        } else {
+            self.duration_ms.unwrap_or_default() as f64
+        }
+    }
+    
+    /// Operation name for tables, marking the `--streaming` rerun.
+    fn label(&self) -> String {
+        if self.streaming {
+            format!("{} (streaming)", self.operation)
+        } else {
+            self.operation.clone()
+        }
+    }
+    
+    /// Identifies the operation across runs. With `--streaming` every operation name
+    /// appears twice, once per engine.
+    fn key(&self) -> (&str, bool) {
+        (&self.operation, self.streaming)
+    }
+}
+
+type BenchmarkFn<'a> = Box<dyn Fn(&LazyFrame, &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> + 'a>;
+
+/// Named benchmarks, run in the order they were registered.
+struct BenchmarkRegistry<'a> {
+    benchmarks: HashMap<String, BenchmarkFn<'a>>,
+    order: Vec<String>,
+}
+
+impl<'a> BenchmarkRegistry<'a> {
+    fn new() -> Self {
+        BenchmarkRegistry { benchmarks: HashMap::new(), order: Vec::new() }
+    }
+    
+    /// Adds `f` under `name`, replacing (but keeping the position of) any benchmark
+    /// already registered under that name.
+    fn register(&mut self, name: &str, f: impl Fn(&LazyFrame, &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> + 'a) {
+        if self.benchmarks.insert(name.to_string(), Box::new(f)).is_none() {
+            self.order.push(name.to_string());
+        }
+    }
+    
+    /// Runs the benchmarks named in `selected` against `df`, or all of them when
+    /// `selected` is empty. Unknown names are an error, so typos do not silently
+    /// produce an empty run.
+    ///
+    /// With `--streaming` each benchmark then runs again on `df` with streaming
+    /// enabled. A benchmark that fails on the streaming engine is recorded as one
+    /// result with a `skipped_reason` and no duration instead of failing the run.
+    ///
+    /// Each benchmark run is sampled by a `PeakMemorySampler`, whose peak fills in
+    /// `peak_memory_mb` on the results that do not record their own.
+    fn run(&self, df: &LazyFrame, opts: &BenchmarkOptions, selected: &[String]) -> PolarsResult<Vec<BenchmarkResult>> {
+        let mut results = Vec::new();
+        for name in self.selected(selected)? {
+            println!("🔄 Running {} benchmark...", name);
+            results.extend(self.run_sampled(name, df, opts)?);
+            if opts.streaming {
+                println!("🔄 Running {} benchmark on the streaming engine...", name);
+                match self.run_sampled(name, &df.clone().with_streaming(true), opts) {
+                    Ok(streamed) => {
+                        results.extend(streamed.into_iter().map(|result| BenchmarkResult { streaming: true, ..result }))
+                    }
+                    Err(e) => {
+                        println!("⚠️  {} is not supported on the streaming engine: {}", name, e);
+                        results.push(BenchmarkResult { streaming: true, ..BenchmarkResult::skipped(name, e.to_string()) });
+                    }
+                }
+            }
+        }
+        Ok(results)
+    }
+    
+    /// Names of the benchmarks `run` would run for `selected`, in registration order.
+    fn selected(&self, selected: &[String]) -> PolarsResult<Vec<&str>> {
+        if let Some(unknown) = selected.iter().find(|name| !self.benchmarks.contains_key(*name)) {
+            return Err(PolarsError::ComputeError(
+                format!("unknown benchmark `{}`, expected one of: {}", unknown, self.order.join(", ")).into(),
+            ));
+        }
+        Ok(self
+            .order
+            .iter()
+            .filter(|name| selected.is_empty() || selected.contains(name))
+            .map(String::as_str)
+            .collect())
+    }
+    
+    /// Runs the benchmark registered under `name` `--warmup` times, discarding the
+    /// results, then once more while sampling peak memory.
+    fn run_sampled(&self, name: &str, df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+        for run in 1..=opts.warmup_runs {
+            println!("  ↺ warming up {} ({}/{})...", name, run, opts.warmup_runs);
+            self.benchmarks[name](df, opts)?;
+        }
+        let sampler = PeakMemorySampler::start();
+        let results = self.benchmarks[name](df, opts);
+        let peak_memory_mb = sampler.stop();
+        Ok(results?
+            .into_iter()
+            .map(|result| BenchmarkResult { peak_memory_mb: result.peak_memory_mb.or(Some(peak_memory_mb)), ..result })
+            .collect())
+    }
+}
+
+/// Sums the row hashes of `df` with fixed seeds, so the checksum does not depend on
+/// row order (group by output is unordered) and is stable across runs.
+///
+/// This is Polars' own seeded row hashing, so it is only comparable between Rust
+/// runs built against the same Polars version. It cannot be matched against a
+/// Python-side hash without an identical hashing implementation there.
+fn output_checksum(df: &DataFrame) -> PolarsResult<u64> {
+    // Row hashing does not support list columns, so those are hashed one element per row.
+    let list_columns: Vec<&str> = df
+        .get_columns()
+        .iter()
+        .filter(|s| matches!(s.dtype(), DataType::List(_)))
+        .map(|s| s.name())
+        .collect();
+    let mut hashable = if list_columns.is_empty() { df.clone() } else { df.explode(list_columns)? };
+    
+    let hasher = polars::export::ahash::RandomState::with_seeds(0, 1, 2, 3);
+    let hashes = hashable.hash_rows(Some(hasher))?;
+    Ok(hashes.into_no_null_iter().fold(0, u64::wrapping_add))
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 struct BenchmarkSuite {
     timestamp: String,
     results: Vec<BenchmarkResult>,
+    /// Results of every `--thread-scaling-test` run, keyed by its thread count
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    thread_scaling: HashMap<usize, Vec<BenchmarkResult>>,
+    /// Discarded runs of each benchmark before its timed run, see `--warmup`
+    #[serde(default)]
+    warmup_runs: u32,
     system_info: SystemInfo,
     dataset_info: DatasetInfo,
 }
@@ -42,19 +709,81 @@ struct SystemInfo {
     os: String,
     cpu_count: usize,
     total_memory_gb: u64,
+    /// Threads in the Polars thread pool, which `POLARS_MAX_THREADS` can lower
+    #[serde(default)]
+    polars_threads: usize,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 struct DatasetInfo {
     source: String,
+    /// Format the dataset was scanned as, see `--format`
+    #[serde(default)]
+    format: InputFormat,
     rows_limit: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    avg_string_len_bytes: Option<usize>,
+    /// Bytes per row of the dataset in memory, see `estimated_row_width_bytes`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    estimated_row_width_bytes: Option<usize>,
+    /// Size of the local cache file, when `--local-cache-path` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bytes_read: Option<u64>,
+    /// Whether the Parquet files written by the bloom filter benchmark carried bloom filters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bloom_filter_enabled: Option<bool>,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    // `--select` is `--benchmark` restricted to the shared benchmarks, so every run
+    // mode only has to look at `args.benchmark`.
+    if !args.select.is_empty() {
+        args.benchmark = args.select.iter().map(|kind| kind.name().to_string()).collect();
+    }
+    // A child of `run_child_suite` or `run_with_timeouts` runs the benchmarks it was
+    // given and leaves timeouts, thread scaling, placeholders and reports to the parent.
+    if let Some(names) = args.child_benchmarks.take() {
+        args.benchmark = names.split(',').filter(|name| !name.is_empty()).map(str::to_string).collect();
+        args.select.clear();
+        args.timeout_secs = None;
+        args.thread_scaling_test = false;
+        args.compare = None;
+        args.regression_suite = None;
+        // The parent reads the child's results back as JSON.
+        args.output_format = OutputFormat::Json;
+    }
+    let extension = args.output_format.extension();
+    if args.output_format != OutputFormat::Json && args.output.extension() != Some(extension.as_ref()) {
+        let corrected = args.output.with_extension(extension);
+        eprintln!("⚠️  Writing {} output to {} instead of {}", extension, corrected.display(), args.output.display());
+        args.output = corrected;
+    }
+    let opts = BenchmarkOptions::new(&args, args.limit_rows);
+    // Read the baseline before running anything, so a bad path fails fast.
+    let baseline = match &args.regression_suite {
+        Some(path) => Some(serde_json::from_slice::<BenchmarkSuite>(&std::fs::read(path)?)?),
+        None => None,
+    };
+    let python_suite = match &args.compare {
+        Some(path) => {
+            let json = std::fs::read(path)
+                .map_err(|e| format!("could not read Python results {}: {}", path.display(), e))?;
+            let suite: BenchmarkSuite = serde_json::from_slice(&json)
+                .map_err(|e| format!("{} is not a benchmark results file: {}", path.display(), e))?;
+            Some(suite)
+        }
+        None => None,
+    };
+    
+    // `LazyCsvReader` only reads local files, so fail here with a clear message
+    // instead of with whatever the reader makes of an S3 URL.
+    if args.format == InputFormat::Csv && args.data_path.starts_with("s3://") {
+        return Err(format!("--format csv needs a local file, not {}", args.data_path).into());
+    }
     
     println!("🦀 Starting Rust Polars benchmarks...");
-    println!("📁 Data source: {}", args.data_path);
+    println!("📁 Data source: {} ({})", args.data_path, args.format.name());
     if let Some(limit) = args.limit_rows {
         println!("📊 Row limit: {}", limit);
     }
@@ -66,14 +795,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         os: system.name().unwrap_or_else(|| "Unknown".to_string()),
         cpu_count: system.cpus().len(),
         total_memory_gb: system.total_memory() / 1_024 / 1_024 / 1_024,
+        polars_threads: polars_core::POOL.current_num_threads(),
     };
     
-    let dataset_info = DatasetInfo {
+    let dataset_info = RefCell::new(DatasetInfo {
         source: args.data_path.clone(),
+        format: args.format,
         rows_limit: args.limit_rows,
-    };
-    
-    let mut results = Vec::new();
+        avg_string_len_bytes: None,
+        estimated_row_width_bytes: None,
+        bytes_read: None,
+        bloom_filter_enabled: None,
+    });
     
     let scan_args = ScanArgsParquet {
         n_rows: args.limit_rows,
@@ -86,195 +819,689 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         use_statistics: true,
     };
     
-    println!("🔄 Running read benchmark...");
-    let result = benchmark_read(&args.data_path, &scan_args)?;
-    results.push(result);
-    
-    let df = LazyFrame::scan_parquet(&args.data_path, scan_args.clone())?;
-    
-    println!("🔄 Running filter benchmark...");
-    let result = benchmark_filter(&df)?;
-    results.push(result);
+    let df = scan_dataset(&args, &scan_args)?;
     
-    println!("🔄 Running aggregation benchmark...");
-    let result = benchmark_aggregation(&df)?;
-    results.push(result);
-    
-    println!("🔄 Running group by benchmark...");
-    let result = benchmark_group_by(&df)?;
-    results.push(result);
+    let mut registry = BenchmarkRegistry::new();
+    registry.register("read", |_, opts| {
+        let scan_args = ScanArgsParquet { n_rows: opts.row_limit, ..scan_args.clone() };
+        let schema = scan_dataset(&args, &scan_args)?.schema()?;
+        let read_result = benchmark_read_dataset(&args, &scan_args, opts)?;
+        dataset_info.borrow_mut().estimated_row_width_bytes = Some(estimated_row_width_bytes(&schema, &read_result));
+        Ok(vec![read_result])
+    });
+    if let Some(cache_path) = &args.local_cache_path {
+        registry.register("remote_vs_local_read", |_, opts| {
+            let (read_results, bytes_read) =
+                benchmark_read_remote_vs_local(&args.data_path, cache_path, &scan_args, opts)?;
+            dataset_info.borrow_mut().bytes_read = Some(bytes_read);
+            Ok(read_results)
+        });
+    }
+    registry.register("filter", |df, opts| Ok(vec![benchmark_filter(df, opts)?]));
+    registry.register("sparse_filter", benchmark_sparse_filter);
+    registry.register("take_vs_filter", benchmark_take_vs_filter);
+    registry.register("aggregation", |df, opts| Ok(vec![benchmark_aggregation(df, opts)?]));
+    registry.register("group_by", |df, opts| Ok(vec![benchmark_group_by(df, opts)?]));
+    registry.register("sort", |df, opts| Ok(vec![benchmark_sort(df, opts)?]));
+    registry.register("window_functions", benchmark_window_functions);
+    registry.register("complex_query", |df, opts| Ok(vec![benchmark_complex_query(df, opts)?]));
+    registry.register("join", |df, opts| {
+        let scan_args = ScanArgsParquet { n_rows: opts.row_limit, ..scan_args.clone() };
+        benchmark_join(df, scan_dataset(&args, &scan_args)?, opts)
+    });
+    registry.register("parallel_collect", |df, opts| {
+        Ok(vec![benchmark_parallel_collect(df, args.parallel_queries as usize, opts)?])
+    });
+    registry.register("groupby_maintain_order", benchmark_groupby_maintain_order);
+    registry.register("groupby_aggregation_variety", benchmark_groupby_aggregation_variety);
+    registry.register("regex_replace", benchmark_regex_replace);
+    registry.register("string_encoding", benchmark_string_encoding);
+    registry.register("coalesce", benchmark_coalesce);
+    registry.register("when_then_otherwise", benchmark_when_then_otherwise);
+    registry.register("cut_qcut", |df, opts| Ok(vec![benchmark_cut(df, opts)?, benchmark_qcut(df, opts)?]));
+    registry.register("clip", benchmark_clip);
+    registry.register("unary_numeric", benchmark_unary_numeric);
+    registry.register("boolean_mask", benchmark_boolean_mask_operations);
+    registry.register("string_split_join", |df, opts| {
+        let (split_result, tokens) = benchmark_string_split_to_list(df, opts)?;
+        Ok(vec![split_result, benchmark_list_to_string(&tokens, opts)?])
+    });
+    registry.register("filter_groupby_order", benchmark_filter_groupby_order);
+    registry.register("nested_groupby", benchmark_nested_groupby);
+    registry.register("into_chunks", |df, opts| benchmark_into_chunks(df, &args.chunk_sizes, opts));
+    registry.register("frame_equality", benchmark_frame_equality);
+    registry.register("select_all_columns", benchmark_select_all_columns);
+    registry.register("series_extend", |_, opts| benchmark_extend(opts));
+    registry.register("schema_validation", benchmark_schema_validation);
+    registry.register("series_to_vec", benchmark_series_to_vec);
+    registry.register("schema_inference", benchmark_lazy_frame_schema_inference);
+    registry.register("null_count", benchmark_null_count);
+    registry.register("all_any", benchmark_all_any);
+    registry.register("running_totals", benchmark_running_totals);
+    registry.register("struct_field_access", benchmark_struct_field_access);
+    registry.register("arg_sort", benchmark_arg_sort);
+    registry.register("mode", benchmark_mode);
+    registry.register("entropy", benchmark_entropy);
+    registry.register("corr_cov", benchmark_corr_cov);
+    registry.register("arg_min_max", benchmark_arg_min_max);
+    registry.register("series_set_operations", benchmark_series_set_operations);
+    registry.register("scan_with_bloom_filter", |df, opts| {
+        dataset_info.borrow_mut().bloom_filter_enabled = Some(false);
+        benchmark_scan_with_bloom_filter(df, opts)
+    });
+    registry.register("expression_fusion", benchmark_expression_fusion);
+    registry.register("null_filter_semantics", benchmark_null_filter_semantics);
+    registry.register("scan_with_statistics_min_max", benchmark_scan_with_statistics_min_max);
+    registry.register("first_last_nth", benchmark_first_last_nth);
+    registry.register("date_arithmetic", benchmark_date_arithmetic);
+    registry.register("timeseries", benchmark_timeseries);
+    registry.register("bitwise_operations", benchmark_bitwise_operations);
+    registry.register("encode_decode_utf8", benchmark_encode_decode_utf8);
+    registry.register("approx_unique", benchmark_approx_unique);
+    registry.register("take_rechunk_vs_gather", benchmark_take_rechunk_vs_gather);
+    registry.register("numeric_dtype_arithmetic", benchmark_numeric_dtype_arithmetic);
+    registry.register("struct_groupby", benchmark_struct_groupby);
+    // Three full child runs, so only when asked for by name.
+    if args.benchmark.iter().any(|name| name == "thread_scaling") {
+        registry.register("thread_scaling", |_, _| benchmark_thread_scaling());
+    }
+    registry.register("categorical_encoding", benchmark_categorical_encoding);
+    registry.register("rechunk_overhead_in_filter", benchmark_rechunk_overhead_in_filter);
+    registry.register("parallel_sort_distributions", benchmark_parallel_sort_distributions);
+    registry.register("pipeline_depth", benchmark_pipeline_depth);
+    registry.register("read_metadata_only", benchmark_read_metadata_only);
+    registry.register("rolling_std_var", benchmark_rolling_std_var);
+    registry.register("list_explode_roundtrip", benchmark_list_explode_roundtrip);
+    registry.register("cross_validation_split", benchmark_cross_validation_split);
+    registry.register("horizontal_concat_frames", benchmark_horizontal_concat_frames);
+    registry.register("dataframe_melt", benchmark_dataframe_melt);
+    registry.register("series_creation", |_, _| benchmark_series_creation());
+    registry.register("chained_vs_fused", benchmark_chained_vs_fused);
+    registry.register("string_normalization", benchmark_string_normalization);
+    registry.register("nested_struct", benchmark_nested_struct);
+    registry.register("partitioned_scan", benchmark_partitioned_scan);
+    registry.register("streaming_groupby", benchmark_streaming_groupby);
+    registry.register("zip", |df, opts| benchmark_zip(df, args.check_asm, opts));
+    registry.register("from_arrow", benchmark_from_arrow);
+    registry.register("tail_and_head", benchmark_tail_and_head);
+    registry.register("schema_cast_all_columns", benchmark_schema_cast_all_columns);
+    registry.register("cols_vs_separate", benchmark_cols_vs_separate);
+    registry.register("read_with_schema_override", benchmark_read_with_schema_override);
+    registry.register("unique_subsets", benchmark_unique_subsets);
+    registry.register("write_csv_options", benchmark_write_csv_options);
+    registry.register("predicate_split", benchmark_predicate_split);
+    registry.register("collect_vs_sink", |df, _| Ok(vec![benchmark_collect_vs_sink(df)?]));
+    registry.register("groupby_apply", benchmark_groupby_apply);
+    registry.register("string_extract", benchmark_string_extract);
+    registry.register("rank_methods", benchmark_rank_methods);
+    registry.register("schema_mismatch", benchmark_schema_mismatch);
+    registry.register("clone", |_, _| benchmark_clone());
+    registry.register("sort_stability", benchmark_sort_stability_comparison);
+    registry.register("series_builder", |_, _| benchmark_series_builder());
+    registry.register("datetime_parsing", benchmark_datetime_parsing);
+    registry.register("write_ipc_stream", benchmark_write_ipc_stream);
+    registry.register("column_rename", benchmark_column_rename);
+    registry.register("cross_join_streaming", |df, opts| benchmark_cross_join_streaming(df, args.max_output_rows, opts));
+    registry.register("column_statistics", benchmark_column_statistics);
+    registry.register("list_sort", benchmark_list_sort);
+    registry.register("arg_group_by", benchmark_arg_group_by);
+    registry.register("format_integers", benchmark_format_integers);
+    registry.register("replace_many", benchmark_replace_many);
+    registry.register("memory_estimate_accuracy", benchmark_memory_estimate_accuracy);
+    registry.register("groupby_cardinality_scaling", benchmark_groupby_cardinality_scaling);
+    registry.register("string_predicates", benchmark_string_predicates);
+    registry.register("profile_query", |df, opts| benchmark_profile_query(df, &args.output, opts));
+    registry.register("statistics_parsing", benchmark_statistics_parsing);
+    registry.register("groupby_apply_closure", benchmark_groupby_apply_closure);
+    registry.register("semi_anti_join", benchmark_semi_anti_join);
+    registry.register("glob_expansion_overhead", benchmark_glob_expansion_overhead);
+    registry.register("row_group_size", benchmark_row_group_size);
+    registry.register("row_vs_column_construction", |_, opts| benchmark_row_vs_column_construction(opts));
+    registry.register("list_operations", benchmark_list_operations);
+    registry.register("list_arithmetic", benchmark_list_arithmetic);
+    registry.register("write_partitioned_parquet", benchmark_write_partitioned_parquet);
+    registry.register("arrow_chunked_array", benchmark_arrow_chunked_array);
+    if let Some(avg_len) = &args.generate_long_strings {
+        registry.register("long_string", |df, opts| {
+            let (long_string_results, avg_string_len) = benchmark_string_chunk_scan(df, *avg_len, opts)?;
+            dataset_info.borrow_mut().avg_string_len_bytes = Some(avg_string_len);
+            Ok(long_string_results)
+        });
+    }
+    if args.force_external_sort {
+        registry.register("external_sort", |df, opts| Ok(vec![benchmark_external_sort(df, opts)?]));
+    }
+    if args.generate_json_column {
+        registry.register("json_extraction", |df, opts| {
+            let json_frame = json_column_frame(df, opts)?;
+            Ok(vec![benchmark_json_extraction(&json_frame, opts)?])
+        });
+    }
     
-    println!("🔄 Running sort benchmark...");
-    let result = benchmark_sort(&df)?;
-    results.push(result);
+    let iterations = match args.iterations {
+        Some(_) if args.data_path.starts_with("s3://") => {
+            eprintln!("⚠️  Ignoring --iterations for an S3 dataset: repeated reads would measure network bandwidth");
+            None
+        }
+        iterations => iterations,
+    };
     
-    println!("🔄 Running complex query benchmark...");
-    let result = benchmark_complex_query(&df)?;
-    results.push(result);
+    let mut thread_scaling = HashMap::new();
+    let mut results = if args.thread_scaling_test {
+        thread_scaling = run_thread_scaling_test(&args)?;
+        // The top-level results are those of the largest pool.
+        let largest = thread_scaling.keys().max().copied().unwrap_or_default();
+        thread_scaling.get(&largest).cloned().unwrap_or_default()
+    } else if let Some(timeout_secs) = args.timeout_secs {
+        let names: Vec<String> = registry.selected(&args.benchmark)?.into_iter().map(str::to_string).collect();
+        run_with_timeouts(&names, Duration::from_secs(timeout_secs), &dataset_info)?
+    } else if args.scaling_test {
+        run_scaling_test(&registry, &args, &scan_args)?
+    } else if let Some(iterations) = iterations {
+        run_iterations(&registry, &df, &opts, &args.benchmark, iterations)?
+    } else {
+        registry.run(&df, &opts, &args.benchmark)?
+    };
+    results.extend(unselected_results(&args.select, args.format));
+    // The registered closures borrow `dataset_info`.
+    drop(registry);
     
     let benchmark_suite = BenchmarkSuite {
         timestamp: chrono::Utc::now().to_rfc3339(),
         results,
+        thread_scaling,
+        warmup_runs: args.warmup,
         system_info,
-        dataset_info,
+        dataset_info: dataset_info.into_inner(),
     };
     
+    let comparison = python_suite
+        .as_ref()
+        .map(|python_suite| comparison::compare_suites(&benchmark_suite, python_suite));
+    
     std::fs::create_dir_all(args.output.parent().unwrap())?;
-    let json = serde_json::to_string_pretty(&benchmark_suite)?;
-    std::fs::write(&args.output, json)?;
+    let formatter: Box<dyn ResultsFormatter> = match args.output_format {
+        OutputFormat::Json => Box::new(output::JsonFormatter),
+        OutputFormat::Csv => Box::new(output::CsvFormatter),
+        OutputFormat::Markdown => Box::new(output::MarkdownFormatter {
+            speedups: comparison
+                .iter()
+                .flatten()
+                .filter_map(|entry| Some((entry.operation.clone(), entry.speedup_factor?)))
+                .collect(),
+        }),
+    };
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(&args.output)?);
+    formatter.write(&benchmark_suite, &mut writer)?;
+    std::io::Write::flush(&mut writer)?;
     
     println!("✅ Benchmarks completed! Results saved to: {}", args.output.display());
     println!("\n📊 Results Summary:");
     for result in &benchmark_suite.results {
-        println!("  • {}: {}ms ({}MB)", result.operation, result.duration_ms, result.memory_mb);
+        let engine = if result.streaming { " [streaming]" } else { "" };
+        match &result.skipped_reason {
+            Some(reason) => println!("  • {}{}: skipped ({})", result.operation, engine, reason),
+            None => println!(
+                "  • {}{}: {}ms ({}MB)",
+                result.operation,
+                engine,
+                result.duration_ms.unwrap_or_default(),
+                result.memory_mb
+            ),
+        }
+    }
+    
+    if let Some(entries) = &comparison {
+        for entry in entries.iter().filter(|entry| entry.checksum_mismatch) {
+            println!("⚠️  {} produced a different output_checksum in Rust and Python", entry.operation);
+        }
+        let report_path = args.output.with_file_name("comparison_report.json");
+        std::fs::write(&report_path, serde_json::to_string_pretty(entries)?)?;
+        println!("⚖️  {}", comparison::summary_line(entries));
+        println!("⚖️  Comparison report saved to: {}", report_path.display());
+    }
+    
+    if let (Some(baseline), Some(baseline_path)) = (&baseline, &args.regression_suite) {
+        regression::print_diff_table(baseline, &benchmark_suite, args.regression_threshold);
+        let regressions = regression::compare_suites(baseline, &benchmark_suite, args.regression_threshold);
+        let report = regression::RegressionReport {
+            baseline: &baseline_path.to_string_lossy(),
+            threshold: args.regression_threshold,
+            regressions: &regressions,
+        };
+        let report_path = args.output.with_file_name("regression_report.json");
+        std::fs::write(&report_path, serde_json::to_string_pretty(&report)?)?;
+        println!("📉 Regression report saved to: {}", report_path.display());
+        if !regressions.is_empty() {
+            return Err(format!(
+                "{} operation(s) regressed by more than {:.0}% against {}",
+                regressions.len(),
+                args.regression_threshold * 100.0,
+                baseline_path.display()
+            )
+            .into());
+        }
     }
     
     Ok(())
 }
 
-fn benchmark_read(path: &str, scan_args: &ScanArgsParquet) -> PolarsResult<BenchmarkResult> {
-    let mut system = System::new_all();
-    system.refresh_memory();
-    let initial_memory = system.used_memory();
+/// Reruns the selected benchmarks in a child process for each `--thread-counts` value
+/// and for the default pool size, see `run_child_suite`. Every result gets the
+/// `thread_count` it ran with and its `parallelism_efficiency`: the speedup over the
+/// same operation at the smallest thread count, divided by the extra threads, so 1.0
+/// is perfect scaling. The `thread_scaling` benchmark is left out of the children,
+/// since it would start three more child runs of its own for every thread count.
+fn run_thread_scaling_test(args: &Args) -> PolarsResult<HashMap<usize, Vec<BenchmarkResult>>> {
+    let benchmarks: Vec<String> = args.benchmark.iter().filter(|name| *name != "thread_scaling").cloned().collect();
+    if benchmarks.is_empty() && !args.benchmark.is_empty() {
+        return Err(PolarsError::ComputeError(
+            "--thread-scaling-test supersedes the thread_scaling benchmark; select other benchmarks".into(),
+        ));
+    }
+    let mut thread_counts: Vec<usize> = args.thread_counts.iter().map(|&threads| threads as usize).collect();
+    thread_counts.push(polars_core::POOL.current_num_threads());
+    thread_counts.sort_unstable();
+    thread_counts.dedup();
+    let dir = TempDirGuard::new("polars-benchmark-thread-scaling")?;
     
-    let start = Instant::now();
-    let df = LazyFrame::scan_parquet(path, scan_args.clone())?.collect()?;
-    let duration = start.elapsed();
+    let mut runs = HashMap::new();
+    for &threads in &thread_counts {
+        println!("🧵 Running with {} threads", threads);
+        let output = dir.0.join(format!("threads_{}.json", threads));
+        let threads_var = threads.to_string();
+        let suite = run_child_suite(&benchmarks, &[("POLARS_MAX_THREADS", threads_var.as_ref())], &output)?;
+        runs.insert(threads, suite.results);
+    }
     
-    system.refresh_memory();
-    let final_memory = system.used_memory();
-    let memory_used = ((final_memory.saturating_sub(initial_memory)) / 1_024 / 1_024) as u64;
+    let base_threads = thread_counts[0];
+    let base_us: HashMap<String, u64> = runs[&base_threads]
+        .iter()
+        .map(|result| (result.operation.clone(), result.duration_us))
+        .collect();
+    for (&threads, results) in &mut runs {
+        for result in results.iter_mut() {
+            result.thread_count = Some(threads);
+            result.parallelism_efficiency = base_us.get(&result.operation).and_then(|&base| {
+                let scaled = result.duration_us as f64 * threads as f64;
+                (scaled > 0.0).then(|| base as f64 * base_threads as f64 / scaled)
+            });
+        }
+    }
     
-    Ok(BenchmarkResult {
-        operation: "read_parquet".to_string(),
-        duration_ms: duration.as_millis() as u64,
-        memory_mb: memory_used,
-        rows_processed: Some(df.height()),
-    })
+    Ok(runs)
 }
 
-fn benchmark_filter(df: &LazyFrame) -> PolarsResult<BenchmarkResult> {
-    let mut system = System::new_all();
-    system.refresh_memory();
-    let initial_memory = system.used_memory();
+/// Runs the selected benchmarks once as a warmup, discarding the results, then
+/// `iterations` more times, and summarizes the timed runs with `summarize_iterations`.
+fn run_iterations(
+    registry: &BenchmarkRegistry,
+    df: &LazyFrame,
+    opts: &BenchmarkOptions,
+    selected: &[String],
+    iterations: u32,
+) -> PolarsResult<Vec<BenchmarkResult>> {
+    println!("🔥 Warmup run");
+    registry.run(df, opts, selected)?;
+    let mut runs = Vec::new();
+    for iteration in 1..=iterations {
+        println!("🔁 Iteration {}/{}", iteration, iterations);
+        runs.push(registry.run(df, opts, selected)?);
+    }
+    Ok(summarize_iterations(runs))
+}
+
+/// Placeholder results for the shared benchmarks left out of a non-empty `--select`,
+/// so the output still has a row for each of them.
+fn unselected_results(select: &[BenchmarkKind], format: InputFormat) -> Vec<BenchmarkResult> {
+    if select.is_empty() {
+        return Vec::new();
+    }
+    <BenchmarkKind as clap::ValueEnum>::value_variants()
+        .iter()
+        .filter(|kind| !select.contains(kind))
+        .map(|kind| BenchmarkResult::skipped(&kind.operation(format), "not selected with --select".to_string()))
+        .collect()
+}
+
+/// Merges repeated runs of the same benchmarks into the first run's results, with
+/// each operation's duration replaced by its mean across the runs and the sample
+/// standard deviation in `duration_stddev_ms`.
+fn summarize_iterations(runs: Vec<Vec<BenchmarkResult>>) -> Vec<BenchmarkResult> {
+    let mut durations_us: HashMap<(String, bool), Vec<f64>> = HashMap::new();
+    for result in runs.iter().flatten() {
+        durations_us.entry((result.operation.clone(), result.streaming)).or_default().push(result.duration_us as f64);
+    }
     
-    let start = Instant::now();
-    let result = df
-        .clone()
-        .filter(col("x").gt(lit(0.5)))
-        .collect()?;
-    let duration = start.elapsed();
+    let mut results = runs.into_iter().next().unwrap_or_default();
+    for result in &mut results {
+        let samples = &durations_us[&(result.operation.clone(), result.streaming)];
+        let n = samples.len() as f64;
+        let mean_us = samples.iter().sum::<f64>() / n;
+        let variance = if samples.len() > 1 {
+            samples.iter().map(|us| (us - mean_us).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        result.duration_us = mean_us.round() as u64;
+        result.duration_ms = Some((mean_us / 1_000.0) as u64);
+        result.duration_stddev_ms = Some(variance.sqrt() / 1_000.0);
+        result.iterations = Some(samples.len() as u32);
+    }
+    results
+}
+
+/// Runs the selected benchmarks at 1%, 10% and 100% of the rows (of `--limit-rows`
+/// when set, otherwise of the whole dataset) and returns the full-size results, each
+/// with the `scaling_exponent` fitted across the three sizes.
+fn run_scaling_test(
+    registry: &BenchmarkRegistry,
+    args: &Args,
+    scan_args: &ScanArgsParquet,
+) -> PolarsResult<Vec<BenchmarkResult>> {
+    let total_rows = match args.limit_rows {
+        Some(limit) => limit,
+        None => {
+            let counted = scan_dataset(args, scan_args)?.select([count()]).collect()?;
+            counted.column("count")?.cast(&DataType::UInt64)?.u64()?.get(0).unwrap_or(0) as usize
+        }
+    };
     
-    system.refresh_memory();
-    let final_memory = system.used_memory();
-    let memory_used = ((final_memory.saturating_sub(initial_memory)) / 1_024 / 1_024) as u64;
+    let mut runs = Vec::new();
+    for percent in [1, 10, 100] {
+        let rows = (total_rows * percent / 100).max(1);
+        println!("📏 Scaling test at {}% ({} rows)", percent, rows);
+        let df = scan_dataset(args, &ScanArgsParquet { n_rows: Some(rows), ..scan_args.clone() })?;
+        runs.push((rows, registry.run(&df, &BenchmarkOptions::new(args, Some(rows)), &args.benchmark)?));
+    }
     
-    Ok(BenchmarkResult {
-        operation: "filter".to_string(),
-        duration_ms: duration.as_millis() as u64,
-        memory_mb: memory_used,
-        rows_processed: Some(result.height()),
-    })
+    let (_, mut results) = runs.pop().unwrap_or_default();
+    let full_rows = total_rows.max(1) as f64;
+    for result in &mut results {
+        let points: Vec<(f64, f64)> = runs
+            .iter()
+            .filter_map(|(rows, smaller)| {
+                let matching = smaller.iter().find(|smaller| smaller.key() == result.key())?;
+                Some((*rows as f64, matching.duration_us as f64))
+            })
+            .chain([(full_rows, result.duration_us as f64)])
+            .collect();
+        result.scaling_exponent = scaling_exponent(&points);
+        result.superlinear = result.scaling_exponent.map(|exponent| exponent > 1.2);
+        if result.superlinear == Some(true) {
+            println!("⚠️  {} scales superlinearly (exponent {:.2})", result.operation, result.scaling_exponent.unwrap_or_default());
+        }
+    }
+    
+    Ok(results)
 }
 
-fn benchmark_aggregation(df: &LazyFrame) -> PolarsResult<BenchmarkResult> {
-    let mut system = System::new_all();
-    system.refresh_memory();
-    let initial_memory = system.used_memory();
+/// Least-squares slope of `ln(duration)` against `ln(rows)` over `(rows, duration)`
+/// points, so 1.0 is linear scaling. Points with a zero value cannot be logged and
+/// are skipped; `None` unless at least two distinct row counts remain.
+fn scaling_exponent(points: &[(f64, f64)]) -> Option<f64> {
+    let logged: Vec<(f64, f64)> = points
+        .iter()
+        .filter(|(rows, duration)| *rows > 0.0 && *duration > 0.0)
+        .map(|(rows, duration)| (rows.ln(), duration.ln()))
+        .collect();
+    let n = logged.len() as f64;
+    let mean_x = logged.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logged.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let variance: f64 = logged.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let covariance: f64 = logged.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    (logged.len() >= 2 && variance > 0.0).then(|| covariance / variance)
+}
+
+/// Collects at most `--max-eager-rows` rows of `df`, for benchmarks that need the
+/// frame in memory before timing starts.
+fn collect_capped(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<DataFrame> {
+    capped_scan(df, opts).collect()
+}
+
+/// `df` limited to `--max-eager-rows` rows, for queries whose own output must not be
+/// truncated, such as a sort.
+fn capped_scan(df: &LazyFrame, opts: &BenchmarkOptions) -> LazyFrame {
+    let cap = IdxSize::try_from(opts.max_eager_rows).unwrap_or(IdxSize::MAX);
+    df.clone().limit(cap)
+}
+
+/// Runs `f` and returns its output together with the elapsed wall-clock time
+/// and the growth in this process's resident set size (in MB) across the call.
+fn measure<T>(f: impl FnOnce() -> PolarsResult<T>) -> PolarsResult<(T, Duration, u64)> {
+    let initial_kb = process_rss_kb();
     
     let start = Instant::now();
-    let result = df
-        .clone()
-        .select([
-            col("x").sum().alias("x_sum"),
-            col("y").sum().alias("y_sum"),
-            col("x").mean().alias("x_mean"),
-            col("y").mean().alias("y_mean"),
-            col("id").count().alias("count"),
-        ])
-        .collect()?;
+    let output = f()?;
     let duration = start.elapsed();
     
-    system.refresh_memory();
-    let final_memory = system.used_memory();
-    let memory_used = ((final_memory.saturating_sub(initial_memory)) / 1_024 / 1_024) as u64;
+    let memory_used = process_rss_kb().saturating_sub(initial_kb) / 1_024;
     
-    Ok(BenchmarkResult {
-        operation: "aggregation".to_string(),
-        duration_ms: duration.as_millis() as u64,
-        memory_mb: memory_used,
-        rows_processed: Some(result.height()),
-    })
+    Ok((output, duration, memory_used))
 }
 
-fn benchmark_group_by(df: &LazyFrame) -> PolarsResult<BenchmarkResult> {
-    let mut system = System::new_all();
-    system.refresh_memory();
-    let initial_memory = system.used_memory();
+/// Resident set size of this process in KB, or 0 if it cannot be read. Only this
+/// process is refreshed, so other processes on a shared runner do not show up.
+fn process_rss_kb() -> u64 {
+    let mut system = System::new_with_specifics(RefreshKind::new());
+    process_rss_bytes(&mut system) / 1_024
+}
+
+/// Samples the resident set size of this process on a background thread every
+/// 50 ms until stopped, keeping the highest value seen.
+struct PeakMemorySampler {
+    running: Arc<AtomicBool>,
+    handle: JoinHandle<u64>,
+}
+
+impl PeakMemorySampler {
+    const INTERVAL: Duration = Duration::from_millis(50);
     
-    let start = Instant::now();
-    let result = df
-        .clone()
-        .groupby([col("name")])
-        .agg([
-            col("x").sum().alias("x_sum"),
-            col("y").mean().alias("y_mean"),
-            col("id").count().alias("count")
-        ])
-        .collect()?;
-    let duration = start.elapsed();
+    fn start() -> Self {
+        let running = Arc::new(AtomicBool::new(true));
+        let handle = std::thread::spawn({
+            let running = Arc::clone(&running);
+            move || {
+                let mut system = System::new_with_specifics(RefreshKind::new());
+                let mut peak = process_rss_bytes(&mut system);
+                while running.load(Ordering::Relaxed) {
+                    std::thread::sleep(Self::INTERVAL);
+                    peak = peak.max(process_rss_bytes(&mut system));
+                }
+                peak
+            }
+        });
+        PeakMemorySampler { running, handle }
+    }
     
-    system.refresh_memory();
-    let final_memory = system.used_memory();
-    let memory_used = ((final_memory.saturating_sub(initial_memory)) / 1_024 / 1_024) as u64;
+    /// Stops sampling and returns the peak resident set size in MB.
+    fn stop(self) -> u64 {
+        self.stop_bytes() / 1_024 / 1_024
+    }
     
-    Ok(BenchmarkResult {
-        operation: "group_by".to_string(),
-        duration_ms: duration.as_millis() as u64,
-        memory_mb: memory_used,
-        rows_processed: Some(result.height()),
-    })
+    /// Stops sampling and returns the peak resident set size in bytes.
+    fn stop_bytes(self) -> u64 {
+        self.running.store(false, Ordering::Relaxed);
+        self.handle.join().unwrap_or(0)
+    }
 }
 
-fn benchmark_sort(df: &LazyFrame) -> PolarsResult<BenchmarkResult> {
-    let mut system = System::new_all();
-    system.refresh_memory();
-    let initial_memory = system.used_memory();
+/// Resident set size of this process in bytes, or 0 if it cannot be read.
+fn process_rss_bytes(system: &mut System) -> u64 {
+    let Ok(pid) = sysinfo::get_current_pid() else {
+        return 0;
+    };
+    system.refresh_process_specifics(pid, ProcessRefreshKind::new());
+    system.process(pid).map_or(0, |process| process.memory())
+}
+
+/// Scans the dataset at `data_path` in its `--format`, reading at most
+/// `scan_args.n_rows` rows. The other Parquet scan settings do not apply to CSV or IPC.
+fn scan_dataset(args: &Args, scan_args: &ScanArgsParquet) -> PolarsResult<LazyFrame> {
+    match args.format {
+        InputFormat::Parquet => LazyFrame::scan_parquet(&args.data_path, scan_args.clone()),
+        InputFormat::Csv => {
+            let delimiter = u8::try_from(args.delimiter)
+                .ok()
+                .filter(u8::is_ascii)
+                .ok_or_else(|| PolarsError::ComputeError("--delimiter must be a single ASCII character".into()))?;
+            LazyCsvReader::new(&args.data_path)
+                .with_delimiter(delimiter)
+                .with_n_rows(scan_args.n_rows)
+                .with_try_parse_dates(true)
+                .finish()
+        }
+        InputFormat::Ipc => LazyFrame::scan_ipc(
+            &args.data_path,
+            ScanArgsIpc { n_rows: scan_args.n_rows, cache: scan_args.cache, ..Default::default() },
+        ),
+    }
+}
+
+/// The read benchmark for the dataset's `--format`, named `read_<format>`.
+fn benchmark_read_dataset(args: &Args, scan_args: &ScanArgsParquet, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    if args.format == InputFormat::Parquet {
+        return benchmark_read(&args.data_path, scan_args, opts);
+    }
+    let (df, duration, memory_used) = measure(|| scan_dataset(args, scan_args)?.collect())?;
     
-    let start = Instant::now();
-    // Sort by x column descending - use correct API for Polars 0.32
-    let result = df
-        .clone()
-        .sort("x", SortOptions {
-            descending: true,
-            nulls_last: false,
-            multithreaded: true,
-            maintain_order: false,
+    BenchmarkResult::new(&BenchmarkKind::Read.operation(args.format), duration, memory_used, Some(df.height()))
+        .with_output(&df, opts)
+}
+
+/// In-memory bytes per row of a frame with `schema`, from the scan's schema before
+/// anything is read. Fixed-width columns count their value size. Variable-width
+/// columns such as strings have no size in the schema, so they share whatever part of
+/// the read's `output_size_bytes` per row the fixed-width columns do not account for.
+fn estimated_row_width_bytes(schema: &Schema, read: &BenchmarkResult) -> usize {
+    let fixed_widths: Vec<Option<usize>> = schema
+        .iter_dtypes()
+        .map(|dtype| match dtype.to_physical() {
+            DataType::Boolean | DataType::Int8 | DataType::UInt8 => Some(1),
+            DataType::Int16 | DataType::UInt16 => Some(2),
+            DataType::Int32 | DataType::UInt32 | DataType::Float32 => Some(4),
+            DataType::Int64 | DataType::UInt64 | DataType::Float64 => Some(8),
+            _ => None,
         })
-        .collect()?;
-    let duration = start.elapsed();
+        .collect();
+    let fixed: usize = fixed_widths.iter().flatten().sum();
+    if fixed_widths.iter().all(Option::is_some) {
+        return fixed;
+    }
+    let measured = match (read.output_size_bytes, read.rows_processed) {
+        (Some(bytes), Some(rows)) if rows > 0 => bytes as usize / rows,
+        _ => 0,
+    };
+    fixed.max(measured)
+}
+
+fn benchmark_read(path: &str, scan_args: &ScanArgsParquet, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    let (df, duration, memory_used) = measure(|| {
+        LazyFrame::scan_parquet(path, scan_args.clone())?.collect()
+    })?;
     
-    system.refresh_memory();
-    let final_memory = system.used_memory();
-    let memory_used = ((final_memory.saturating_sub(initial_memory)) / 1_024 / 1_024) as u64;
+    BenchmarkResult::new("read_parquet", duration, memory_used, Some(df.height())).with_output(&df, opts)
+}
+
+fn benchmark_filter(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    let (result, duration, memory_used) = measure(|| {
+        df.clone()
+            .filter(col("x").gt(lit(0.5)))
+            .collect()
+    })?;
     
-    Ok(BenchmarkResult {
-        operation: "sort".to_string(),
-        duration_ms: duration.as_millis() as u64,
-        memory_mb: memory_used,
-        rows_processed: Some(result.height()),
-    })
+    BenchmarkResult::new("filter", duration, memory_used, Some(result.height())).with_output(&result, opts)
 }
 
-fn benchmark_complex_query(df: &LazyFrame) -> PolarsResult<BenchmarkResult> {
-    let mut system = System::new_all();
-    system.refresh_memory();
-    let initial_memory = system.used_memory();
+fn benchmark_aggregation(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    let (result, duration, memory_used) = measure(|| {
+        df.clone()
+            .select([
+                col("x").sum().alias("x_sum"),
+                col("y").sum().alias("y_sum"),
+                col("x").mean().alias("x_mean"),
+                col("y").mean().alias("y_mean"),
+                col("id").count().alias("count"),
+            ])
+            .collect()
+    })?;
     
-    let start = Instant::now();
-    // Complex query: filter, group by, aggregate, and sort
-    let result = df
-        .clone()
+    BenchmarkResult::new("aggregation", duration, memory_used, Some(result.height())).with_output(&result, opts)
+}
+
+fn benchmark_group_by(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    let (result, duration, memory_used) = measure(|| {
+        df.clone()
+            .groupby([col("name")])
+            .agg([
+                col("x").sum().alias("x_sum"),
+                col("y").mean().alias("y_mean"),
+                col("id").count().alias("count")
+            ])
+            .collect()
+    })?;
+    
+    BenchmarkResult::new("group_by", duration, memory_used, Some(result.height())).with_output(&result, opts)
+}
+
+fn benchmark_sort(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    // Sort by x column descending - use correct API for Polars 0.32
+    let (result, duration, memory_used) = measure(|| {
+        df.clone()
+            .sort("x", SortOptions {
+                descending: true,
+                nulls_last: false,
+                multithreaded: true,
+                maintain_order: false,
+            })
+            .collect()
+    })?;
+    
+    BenchmarkResult::new("sort", duration, memory_used, Some(result.height())).with_output(&result, opts)
+}
+
+/// Sorts by `timestamp` and times a 30-row rolling mean of `x` with a rolling sum of
+/// `y` over the same window (`window_rolling`), then an exponentially weighted mean
+/// of `x` with alpha 0.1 (`window_ewm`). Both timings include the sort.
+fn benchmark_window_functions(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    const WINDOW: usize = 30;
+    let sorted = || df.clone().sort("timestamp", SortOptions::default());
+    let window = || RollingOptions {
+        window_size: polars::prelude::Duration::parse(&format!("{}i", WINDOW)),
+        min_periods: 1,
+        center: false,
+        weights: None,
+        fn_params: None,
+        ..Default::default()
+    };
+    
+    let (rolling, duration, memory_used) = measure(|| {
+        sorted()
+            .select([
+                col("timestamp"),
+                col("x").rolling_mean(window()).alias("x_rolling_mean"),
+                col("y").rolling_sum(window()).alias("y_rolling_sum"),
+            ])
+            .collect()
+    })?;
+    let rolling_result = BenchmarkResult {
+        window_size: Some(WINDOW),
+        ..BenchmarkResult::new("window_rolling", duration, memory_used, Some(rolling.height()))
+    }
+    .with_output(&rolling, opts)?;
+    
+    let ewm = EWMOptions { alpha: 0.1, ..Default::default() };
+    let (ewm_mean, duration, memory_used) =
+        measure(|| sorted().select([col("timestamp"), col("x").ewm_mean(ewm).alias("x_ewm_mean")]).collect())?;
+    let ewm_result = BenchmarkResult::new("window_ewm", duration, memory_used, Some(ewm_mean.height()))
+        .with_output(&ewm_mean, opts)?;
+    
+    Ok(vec![rolling_result, ewm_result])
+}
+
+/// Complex query: filter, group by, aggregate, and sort
+fn complex_query(df: &LazyFrame) -> LazyFrame {
+    df.clone()
         .filter(col("x").gt(lit(0.0)).and(col("y").lt(lit(1.0))))
         .groupby([col("name")])
         .agg([
@@ -288,17 +1515,4412 @@ fn benchmark_complex_query(df: &LazyFrame) -> PolarsResult<BenchmarkResult> {
             multithreaded: true,
             maintain_order: false,
         })
-        .collect()?;
-    let duration = start.elapsed();
+}
+
+fn benchmark_complex_query(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    let (result, duration, memory_used) = measure(|| complex_query(df).collect())?;
     
-    system.refresh_memory();
-    let final_memory = system.used_memory();
-    let memory_used = ((final_memory.saturating_sub(initial_memory)) / 1_024 / 1_024) as u64;
+    BenchmarkResult::new("complex_query", duration, memory_used, Some(result.height())).with_output(&result, opts)
+}
+
+/// Runs `n_queries` copies of the complex query at the same time and compares the
+/// wall-clock time for all of them against the sum of their individual times.
+/// An efficiency near 1.0 means the queries scaled perfectly, near 1/N means they
+/// were effectively serialized on the Polars thread pool.
+fn benchmark_parallel_collect(df: &LazyFrame, n_queries: usize, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    let (runs, duration, memory_used) = measure(|| {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..n_queries)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let start = Instant::now();
+                        let result = complex_query(df).collect()?;
+                        Ok((start.elapsed(), result))
+                    })
+                })
+                .collect();
+            
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().unwrap_or_else(|_| {
+                        Err(PolarsError::ComputeError("parallel query thread panicked".into()))
+                    })
+                })
+                .collect::<PolarsResult<Vec<(Duration, DataFrame)>>>()
+        })
+    })?;
     
-    Ok(BenchmarkResult {
-        operation: "complex_query".to_string(),
-        duration_ms: duration.as_millis() as u64,
-        memory_mb: memory_used,
-        rows_processed: Some(result.height()),
-    })
+    let sum_individual: f64 = runs.iter().map(|(elapsed, _)| elapsed.as_secs_f64()).sum();
+    let wall_clock = duration.as_secs_f64();
+    let parallelism_efficiency = if wall_clock > 0.0 {
+        Some(sum_individual / (n_queries as f64 * wall_clock))
+    } else {
+        None
+    };
+    let rows_processed = runs.iter().map(|(_, result)| result.height()).sum();
+    
+    BenchmarkResult {
+        parallelism_efficiency,
+        ..BenchmarkResult::new("parallel_collect", duration, memory_used, Some(rows_processed))
+    }
+    .with_output(&runs[0].1, opts)
+}
+
+/// Repeats `value` until it is `target_len` bytes long, cutting it at the last
+/// char boundary that fits.
+fn repeat_to_len(value: &str, target_len: usize) -> String {
+    if value.is_empty() {
+        return value.to_string();
+    }
+    let mut repeated = value.repeat(target_len / value.len() + 1);
+    let mut cut = target_len.min(repeated.len());
+    while !repeated.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    repeated.truncate(cut);
+    repeated
+}
+
+/// Replaces the `name` column of up to `--max-eager-rows` rows with long strings built
+/// from the original values, then reruns the filter and group by benchmarks on the result. Returns the
+/// results together with the measured average string length in bytes.
+fn benchmark_string_chunk_scan(df: &LazyFrame, avg_len: usize, opts: &BenchmarkOptions) -> PolarsResult<(Vec<BenchmarkResult>, usize)> {
+    let long_strings = collect_capped(
+        &df.clone().with_column(col("name").map(
+            move |s| {
+                let names = s.utf8()?.apply(|value| repeat_to_len(value, avg_len).into());
+                Ok(Some(names.into_series()))
+            },
+            GetOutput::same_type(),
+        )),
+        opts,
+    )?;
+    
+    let names = long_strings.column("name")?.utf8()?;
+    let total_bytes: usize = names.into_iter().flatten().map(str::len).sum();
+    let avg_string_len = total_bytes / names.len().saturating_sub(names.null_count()).max(1);
+    
+    let long_strings = long_strings.lazy();
+    let mut results = Vec::new();
+    for mut result in [benchmark_filter(&long_strings, opts)?, benchmark_group_by(&long_strings, opts)?] {
+        result.operation = format!("{}_long_strings", result.operation);
+        results.push(result);
+    }
+    
+    Ok((results, avg_string_len))
+}
+
+/// Builds a frame of `n_chunks` chunks with `chunk_rows` rows each by stacking the
+/// first `chunk_rows` rows of the dataset onto itself without rechunking.
+fn fragmented_frame(df: &LazyFrame, n_chunks: usize, chunk_rows: usize) -> PolarsResult<DataFrame> {
+    let mut base = df.clone().limit(chunk_rows as IdxSize).collect()?;
+    base.as_single_chunk_par();
+    
+    let mut frame = base.clone();
+    for _ in 1..n_chunks {
+        frame.vstack_mut(&base)?;
+    }
+    Ok(frame)
+}
+
+/// Copies `frame` into chunks of at most `batch_rows` rows each.
+fn batched_frame(frame: &DataFrame, batch_rows: usize) -> PolarsResult<DataFrame> {
+    let mut single = frame.clone();
+    single.as_single_chunk_par();
+    
+    let mut batched = single.slice(0, batch_rows);
+    for offset in (batch_rows..single.height()).step_by(batch_rows) {
+        batched.vstack_mut(&single.slice(offset as i64, batch_rows))?;
+    }
+    Ok(batched)
+}
+
+/// Compares `sum`, `mean` and `filter` on a frame made of 1000 small chunks against
+/// the same frame after `rechunk()`.
+fn benchmark_arrow_chunked_array(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let fragmented = fragmented_frame(df, 1_000, 1_000)?;
+    let mut rechunked = fragmented.clone();
+    rechunked.as_single_chunk_par();
+    
+    let mut results = Vec::new();
+    for (label, frame) in [("fragmented", &fragmented), ("rechunked", &rechunked)] {
+        let n_chunks_input = Some(frame.n_chunks());
+        let x = frame.column("x")?;
+        
+        let (sum, duration, memory_used) = measure(|| Ok(x.sum::<f64>()))?;
+        results.push(
+            BenchmarkResult {
+                n_chunks_input,
+                ..BenchmarkResult::new(&format!("chunked_sum_{}", label), duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&DataFrame::new(vec![Series::new("sum", [sum])])?, opts)?,
+        );
+        
+        let (mean, duration, memory_used) = measure(|| Ok(x.mean()))?;
+        results.push(
+            BenchmarkResult {
+                n_chunks_input,
+                ..BenchmarkResult::new(&format!("chunked_mean_{}", label), duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&DataFrame::new(vec![Series::new("mean", [mean])])?, opts)?,
+        );
+        
+        let (filtered, duration, memory_used) = measure(|| frame.filter(&x.gt(0.5)?))?;
+        results.push(
+            BenchmarkResult {
+                n_chunks_input,
+                ..BenchmarkResult::new(&format!("chunked_filter_{}", label), duration, memory_used, Some(filtered.height()))
+            }
+            .with_output(&filtered, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Runs a separate group by on `name` for each aggregation function applied to `x`,
+/// so the cost of each aggregation path can be compared in isolation.
+fn benchmark_groupby_aggregation_variety(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let aggregations = [
+        ("sum", col("x").sum()),
+        ("mean", col("x").mean()),
+        ("max", col("x").max()),
+        ("min", col("x").min()),
+        ("std", col("x").std(1)),
+        ("var", col("x").var(1)),
+        // a bare column inside `agg` already collects each group into a list
+        ("list", col("x")),
+        ("first", col("x").first()),
+        ("last", col("x").last()),
+        ("count", col("x").count()),
+    ];
+    
+    let mut results = Vec::new();
+    for (func, agg) in aggregations {
+        let (result, duration, memory_used) = measure(|| {
+            df.clone()
+                .groupby([col("name")])
+                .agg([agg.alias("x_agg")])
+                .collect()
+        })?;
+        results.push(
+            BenchmarkResult::new(&format!("groupby_agg_{}", func), duration, memory_used, Some(result.height()))
+                .with_output(&result, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Filters `x` with thresholds taken from its own distribution so that the
+/// predicates keep 0.001%, 1%, 50% and 99% of the rows.
+fn benchmark_sparse_filter(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let selectivities = [("0001", 0.00001), ("1", 0.01), ("50", 0.5), ("99", 0.99)];
+    
+    let thresholds = df
+        .clone()
+        .select(
+            selectivities
+                .iter()
+                .map(|(label, fraction)| {
+                    col("x")
+                        .quantile(lit(1.0 - fraction), QuantileInterpolOptions::Nearest)
+                        .alias(label)
+                })
+                .collect::<Vec<_>>(),
+        )
+        .collect()?;
+    
+    let mut results = Vec::new();
+    for (label, _) in selectivities {
+        let threshold = thresholds.column(label)?.f64()?.get(0).ok_or_else(|| {
+            PolarsError::ComputeError(format!("no {} quantile for x: column is empty or all null", label).into())
+        })?;
+        let (result, duration, memory_used) = measure(|| {
+            df.clone()
+                .filter(col("x").gt(lit(threshold)))
+                .collect()
+        })?;
+        results.push(
+            BenchmarkResult::new(&format!("filter_selectivity_{}", label), duration, memory_used, Some(result.height()))
+                .with_output(&result, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Deletes a directory when dropped, so files written before an error are removed too.
+struct TempDirGuard(PathBuf);
+
+impl TempDirGuard {
+    /// Creates a fresh directory under the system temp dir, unique to this run.
+    fn new(prefix: &str) -> PolarsResult<Self> {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("{}-{}-{}", prefix, std::process::id(), nanos));
+        std::fs::create_dir(&dir)?;
+        Ok(TempDirGuard(dir))
+    }
+}
+
+impl Drop for TempDirGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Splits up to `--max-eager-rows` rows by `name` and writes every partition to its
+/// own Parquet file in a temporary directory, timing the split and the writes separately.
+fn benchmark_write_partitioned_parquet(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    let out_dir = TempDirGuard::new("polars-benchmark-partitions")?;
+    
+    let (mut partitions, duration, memory_used) = measure(|| frame.partition_by(["name"], true))?;
+    let num_partitions = Some(partitions.len());
+    let output_checksum = if opts.verify_output {
+        let checksums = partitions.iter().map(output_checksum).collect::<PolarsResult<Vec<_>>>()?;
+        Some(checksums.into_iter().fold(0, u64::wrapping_add))
+    } else {
+        None
+    };
+    let partition_result = BenchmarkResult {
+        num_partitions,
+        output_checksum,
+        ..BenchmarkResult::new("partition_by", duration, memory_used, Some(frame.height()))
+    };
+    
+    let (_, duration, memory_used) = measure(|| {
+        for (i, partition) in partitions.iter_mut().enumerate() {
+            let file = std::fs::File::create(out_dir.0.join(format!("part-{:05}.parquet", i)))?;
+            ParquetWriter::new(file).finish(partition)?;
+        }
+        Ok(())
+    })?;
+    let write_result = BenchmarkResult {
+        num_partitions,
+        ..BenchmarkResult::new("write_partitioned_parquet", duration, memory_used, Some(frame.height()))
+    }
+    .with_output(&frame, opts)?;
+    
+    Ok(vec![partition_result, write_result])
+}
+
+/// Runs the group by benchmark query with both `groupby` (unordered) and
+/// `groupby_stable` (groups kept in order of first appearance).
+fn benchmark_groupby_maintain_order(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let aggs = [
+        col("x").sum().alias("x_sum"),
+        col("y").mean().alias("y_mean"),
+        col("id").count().alias("count"),
+    ];
+    
+    let (unstable, duration, memory_used) = measure(|| {
+        df.clone()
+            .groupby([col("name")])
+            .agg(aggs.clone())
+            .collect()
+    })?;
+    let unstable_result = BenchmarkResult::new("groupby_unstable", duration, memory_used, Some(unstable.height()))
+        .with_output(&unstable, opts)?;
+    
+    let (stable, duration, memory_used) = measure(|| {
+        df.clone()
+            .groupby_stable([col("name")])
+            .agg(aggs.clone())
+            .collect()
+    })?;
+    let stable_result = BenchmarkResult::new("groupby_stable", duration, memory_used, Some(stable.height()))
+        .with_output(&stable, opts)?;
+    
+    Ok(vec![unstable_result, stable_result])
+}
+
+/// Ratio of `baseline` to `candidate`, i.e. how many times faster `candidate` ran.
+fn speedup(baseline: Duration, candidate: Duration) -> Option<f64> {
+    let candidate = candidate.as_secs_f64();
+    (candidate > 0.0).then(|| baseline.as_secs_f64() / candidate)
+}
+
+/// Throughput of an operation that handled `rows` rows in `duration`.
+fn rows_per_second(rows: usize, duration: Duration) -> Option<f64> {
+    let seconds = duration.as_secs_f64();
+    (seconds > 0.0).then(|| rows as f64 / seconds)
+}
+
+/// Selects every tenth row of up to `--max-eager-rows` rows once with a boolean mask
+/// and `filter`, and once with an index array and `take`, checking that both produce
+/// the same frame.
+fn benchmark_take_vs_filter(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    let n_rows = frame.height();
+    let mask = BooleanChunked::from_iter_values("mask", (0..n_rows).map(|i| i % 10 == 0));
+    let indices = IdxCa::from_vec("idx", (0..n_rows).step_by(10).map(|i| i as IdxSize).collect());
+    
+    let (filtered, filter_duration, memory_used) = measure(|| frame.filter(&mask))?;
+    let filter_result = BenchmarkResult::new("select_rows_filter", filter_duration, memory_used, Some(filtered.height()))
+        .with_output(&filtered, opts)?;
+    
+    let (taken, take_duration, memory_used) = measure(|| frame.take(&indices))?;
+    let take_result = BenchmarkResult {
+        speedup_ratio: speedup(filter_duration, take_duration),
+        ..BenchmarkResult::new("select_rows_take", take_duration, memory_used, Some(taken.height()))
+    }
+    .with_output(&taken, opts)?;
+    
+    if !filtered.frame_equal_missing(&taken) {
+        return Err(PolarsError::ComputeError(
+            format!(
+                "take and filter selected different rows: filter gave {:?}, take gave {:?}",
+                filtered.shape(),
+                taken.shape()
+            )
+            .into(),
+        ));
+    }
+    
+    Ok(vec![filter_result, take_result])
+}
+
+/// Compares regex `replace` (first match) and `replace_all` on up to
+/// `--max-eager-rows` rows of the `name` column against a `contains` predicate using
+/// the same pattern, to separate the cost of matching from the cost of rewriting the
+/// strings.
+fn benchmark_regex_replace(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    const PATTERN: &str = r"[aeiou]+";
+    let frame = collect_capped(&df.clone().select([col("name")]), opts)?;
+    
+    let variants = [
+        ("regex_contains", col("name").str().contains(lit(PATTERN), false)),
+        ("regex_replace", col("name").str().replace(lit(PATTERN), lit("_"), false)),
+        ("regex_replace_all", col("name").str().replace_all(lit(PATTERN), lit("_"), false)),
+    ];
+    time_expressions(&frame, variants, opts)
+}
+
+/// Collects `x` into one list per `name` and returns the frame with its `x_list`
+/// column, along with the total number of list elements.
+fn list_column_frame(df: &LazyFrame) -> PolarsResult<(DataFrame, usize)> {
+    let lists = df
+        .clone()
+        .groupby([col("name")])
+        .agg([col("x").alias("x_list")])
+        .collect()?;
+    let total_elements = lists
+        .column("x_list")?
+        .list()?
+        .into_iter()
+        .flatten()
+        .map(|list| list.len())
+        .sum();
+    Ok((lists, total_elements))
+}
+
+/// Times list-wise `lengths`, `sum`, `max` and `explode` on a list column produced
+/// by a group by with list aggregation.
+fn benchmark_list_operations(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let (lists, total_list_elements) = list_column_frame(df)?;
+    
+    let operations = [
+        ("list_lengths", col("x_list").list().lengths()),
+        ("list_sum", col("x_list").list().sum()),
+        ("list_max", col("x_list").list().max()),
+        ("list_explode", col("x_list").explode()),
+    ];
+    
+    let mut results = time_selections(&lists, operations, opts)?;
+    for result in &mut results {
+        result.total_list_elements = Some(total_list_elements);
+    }
+    Ok(results)
+}
+
+/// Builds a frame with a `json_str` column holding `{"id": .., "x": .., "name": ..}`
+/// objects for up to `--max-eager-rows` rows. Every hundredth row has no payload.
+fn json_column_frame(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<DataFrame> {
+    let frame = collect_capped(&df.clone().select([col("id"), col("x"), col("name")]), opts)?;
+    let ids = frame.column("id")?.cast(&DataType::Int64)?;
+    let xs = frame.column("x")?.cast(&DataType::Float64)?;
+    
+    let mut json_str: Utf8Chunked = ids
+        .i64()?
+        .into_iter()
+        .zip(xs.f64()?)
+        .zip(frame.column("name")?.utf8()?)
+        .enumerate()
+        .map(|(i, ((id, x), name))| match (id, x, name) {
+            (Some(id), Some(x), Some(name)) if i % 100 != 0 => {
+                Some(format!(r#"{{"id":{},"x":{},"name":"{}"}}"#, id, x, name))
+            }
+            _ => None,
+        })
+        .collect();
+    json_str.rename("json_str");
+    
+    DataFrame::new(vec![json_str.into_series()])
+}
+
+/// Parses the `json_str` column into one column per field. `str().json_extract`
+/// needs the `extract_jsonpath` feature, which does not build against the published
+/// jsonpath_lib 0.3, so the rows go through the NDJSON reader instead: the same
+/// deserializer, fed one document per line. Rows without a payload become `null`
+/// lines and are counted in `parse_failures`.
+fn benchmark_json_extraction(frame: &DataFrame, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    let mut lines = String::new();
+    for value in frame.column("json_str")?.utf8()?.into_iter() {
+        lines.push_str(value.unwrap_or("null"));
+        lines.push('\n');
+    }
+    
+    let (result, duration, memory_used) = measure(|| {
+        JsonLineReader::new(std::io::Cursor::new(lines.as_bytes()))
+            .infer_schema_len(Some(100))
+            .finish()
+    })?;
+    let all_null = result.get_columns().iter().map(|s| s.is_null()).reduce(|a, b| &a & &b);
+    let parse_failures = Some(all_null.map_or(result.height(), |mask| mask.sum().unwrap_or(0) as usize));
+    
+    BenchmarkResult {
+        parse_failures,
+        ..BenchmarkResult::new("json_extract", duration, memory_used, Some(result.height()))
+    }
+    .with_output(&result, opts)
+}
+
+/// Percent-encodes every byte outside the RFC 3986 unreserved set.
+fn url_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Reverses `url_encode`, returning `None` for malformed escapes or a result that
+/// is not valid UTF-8.
+fn url_decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            decoded.push(u8::from_str_radix(value.get(i + 1..i + 3)?, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Applies `f` to the string column `column`. Polars keeps its base64 and hex kernels
+/// on `Utf8Chunked` rather than in the expression namespace, so every variant goes
+/// through the same `map` call.
+fn utf8_map(column: &str, f: fn(&Utf8Chunked) -> PolarsResult<Series>, output: DataType) -> Expr {
+    col(column).map(move |s| f(s.utf8()?).map(Some), GetOutput::from_type(output))
+}
+
+/// Times base64 and hex encoding with Polars' kernels, and URL encoding with a
+/// per-string UDF since Polars has no URL kernel. Hex encoding also runs as a UDF so
+/// `udf_overhead_ratio` shows what a native URL kernel might save.
+fn benchmark_string_encoding(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let names = collect_capped(&df.clone().select([col("name")]), opts)?;
+    let inputs = names
+        .lazy()
+        .with_columns([
+            utf8_map("name", |ca| Ok(ca.base64_encode().into_series()), DataType::Utf8).alias("name_base64"),
+            utf8_map("name", |ca| Ok(ca.apply(|v| url_encode(v).into()).into_series()), DataType::Utf8).alias("name_url"),
+        ])
+        .collect()?;
+    
+    let variants = [
+        ("base64_encode", utf8_map("name", |ca| Ok(ca.base64_encode().into_series()), DataType::Utf8)),
+        ("base64_decode", utf8_map("name_base64", |ca| Ok(ca.base64_decode(true)?.into_series()), DataType::Binary)),
+        ("hex_encode", utf8_map("name", |ca| Ok(ca.hex_encode().into_series()), DataType::Utf8)),
+        (
+            "hex_encode_udf",
+            utf8_map(
+                "name",
+                |ca| Ok(ca.apply(|v| v.bytes().map(|b| format!("{:02x}", b)).collect::<String>().into()).into_series()),
+                DataType::Utf8,
+            ),
+        ),
+        ("url_encode_udf", utf8_map("name", |ca| Ok(ca.apply(|v| url_encode(v).into()).into_series()), DataType::Utf8)),
+        (
+            "url_decode_udf",
+            utf8_map(
+                "name_url",
+                |ca| Ok(ca.into_iter().map(|v| v.and_then(url_decode)).collect::<Utf8Chunked>().into_series()),
+                DataType::Utf8,
+            ),
+        ),
+    ];
+    
+    let mut results = time_selections(&inputs, variants, opts)?;
+    let duration_of = |results: &[BenchmarkResult], operation: &str| {
+        results.iter().find(|result| result.operation == operation).map(|result| Duration::from_micros(result.duration_us))
+    };
+    if let (Some(native), Some(udf)) = (duration_of(&results, "hex_encode"), duration_of(&results, "hex_encode_udf")) {
+        let udf_overhead_ratio = speedup(udf, native);
+        for result in results.iter_mut().filter(|result| result.operation == "hex_encode_udf") {
+            result.udf_overhead_ratio = udf_overhead_ratio;
+        }
+    }
+    Ok(results)
+}
+
+/// Replaces `x` with null on every `n`th row, leaving the other columns untouched.
+fn null_every_nth_x(frame: DataFrame, n: u32) -> PolarsResult<DataFrame> {
+    let columns: Vec<Expr> = frame.get_column_names().iter().map(|name| col(name)).collect();
+    frame
+        .lazy()
+        .with_row_count("row_nr", None)
+        .with_column(
+            when((col("row_nr") % lit(n)).eq(lit(0)))
+                .then(lit(NULL).cast(DataType::Float64))
+                .otherwise(col("x"))
+                .alias("x"),
+        )
+        .select(columns)
+        .collect()
+}
+
+/// Nulls out `x` on every other row, then times three ways of falling back to `y`:
+/// `coalesce`, `fill_null` and a `when/then/otherwise` on `is_null`.
+fn benchmark_coalesce(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let sparse = null_every_nth_x(collect_capped(&df.clone().select([col("x"), col("y")]), opts)?, 2)?;
+    
+    let variants = [
+        ("coalesce", coalesce(&[col("x"), col("y"), lit(0.0)])),
+        ("fill_null", col("x").fill_null(col("y"))),
+        ("when_is_null", when(col("x").is_null()).then(col("y")).otherwise(col("x"))),
+    ];
+    time_expressions(&sparse, variants, opts)
+}
+
+/// Times each expression as a `with_columns` call on `frame`, recording one result
+/// per `(operation, expression)` pair.
+fn time_expressions<S: AsRef<str>>(
+    frame: &DataFrame,
+    variants: impl IntoIterator<Item = (S, Expr)>,
+    opts: &BenchmarkOptions,
+) -> PolarsResult<Vec<BenchmarkResult>> {
+    time_queries(frame, variants, opts, |lazy, expr| lazy.with_columns([expr]))
+}
+
+/// Like `time_expressions`, but each expression is the only column of a `select`,
+/// for expressions that change the row count or whose inputs should not be part of
+/// the output.
+fn time_selections<S: AsRef<str>>(
+    frame: &DataFrame,
+    variants: impl IntoIterator<Item = (S, Expr)>,
+    opts: &BenchmarkOptions,
+) -> PolarsResult<Vec<BenchmarkResult>> {
+    time_queries(frame, variants, opts, |lazy, expr| lazy.select([expr]))
+}
+
+/// The loop behind `time_expressions` and `time_selections`, with `query` putting the
+/// expression, aliased `result`, into the frame.
+fn time_queries<S: AsRef<str>>(
+    frame: &DataFrame,
+    variants: impl IntoIterator<Item = (S, Expr)>,
+    opts: &BenchmarkOptions,
+    query: fn(LazyFrame, Expr) -> LazyFrame,
+) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut results = Vec::new();
+    for (operation, expr) in variants {
+        let (result, duration, memory_used) = measure(|| query(frame.clone().lazy(), expr.alias("result")).collect())?;
+        results.push(
+            BenchmarkResult::new(operation.as_ref(), duration, memory_used, Some(result.height()))
+                .with_output(&result, opts)?,
+        );
+    }
+    Ok(results)
+}
+
+/// Buckets `x` with `depth` nested `when/then/otherwise` expressions, using evenly
+/// spaced thresholds across `[-1, 1]`.
+fn when_then_chain(depth: usize) -> Expr {
+    (0..depth).rev().fold(lit(depth as i32), |otherwise, i| {
+        let threshold = -1.0 + 2.0 * (i + 1) as f64 / (depth + 1) as f64;
+        when(col("x").lt(lit(threshold))).then(lit(i as i32)).otherwise(otherwise)
+    })
+}
+
+/// Times `when/then/otherwise` chains of depth 1, 3, 5 and 10 on `x`, where depth 10
+/// is a decile-style bucketing.
+fn benchmark_when_then_otherwise(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?;
+    let variants: Vec<_> = [1, 3, 5, 10]
+        .into_iter()
+        .map(|depth| (format!("when_then_depth_{}", depth), when_then_chain(depth)))
+        .collect();
+    time_expressions(&frame, variants, opts)
+}
+
+/// Times binning `x` with `expr` and records the number of bins and the dtype of
+/// the binned column.
+fn benchmark_binning(
+    df: &LazyFrame,
+    operation: &str,
+    expr: Expr,
+    num_bins: usize,
+    opts: &BenchmarkOptions,
+) -> PolarsResult<BenchmarkResult> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?;
+    let (result, duration, memory_used) = measure(|| {
+        frame.lazy()
+            .with_columns([expr.alias("x_bin")])
+            .collect()
+    })?;
+    
+    BenchmarkResult {
+        num_bins: Some(num_bins),
+        output_dtype: Some(result.column("x_bin")?.dtype().to_string()),
+        ..BenchmarkResult::new(operation, duration, memory_used, Some(result.height()))
+    }
+    .with_output(&result, opts)
+}
+
+/// Bins `x` with `cut` on fixed breaks.
+fn benchmark_cut(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    let breaks = vec![0.0, 0.25, 0.5, 0.75, 1.0];
+    let num_bins = breaks.len() + 1;
+    benchmark_binning(df, "cut", col("x").cut(breaks, None, false, false), num_bins, opts)
+}
+
+/// Bins `x` with `qcut`, which has to compute the quantile breaks before binning.
+fn benchmark_qcut(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    let probs = vec![0.25, 0.5, 0.75];
+    let num_bins = probs.len() + 1;
+    benchmark_binning(df, "qcut", col("x").qcut(probs, None, false, false, false), num_bins, opts)
+}
+
+/// Times `clip`, `clip_min` and `clip_max` on `x` against the equivalent
+/// `when/then/otherwise` clamp to `[0.1, 0.9]`.
+fn benchmark_clip(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?;
+    let (lower, upper) = (0.1, 0.9);
+    
+    let variants = [
+        ("clip", col("x").clip(AnyValue::Float64(lower), AnyValue::Float64(upper))),
+        ("clip_min", col("x").clip_min(AnyValue::Float64(lower))),
+        ("clip_max", col("x").clip_max(AnyValue::Float64(upper))),
+        (
+            "clip_when_then",
+            when(col("x").lt(lit(lower)))
+                .then(lit(lower))
+                .when(col("x").gt(lit(upper)))
+                .then(lit(upper))
+                .otherwise(col("x")),
+        ),
+    ];
+    // Clipped again outside the timing, so the timed runs stay the same as for the
+    // other `time_expressions` benchmarks.
+    let clamped_fractions = variants
+        .iter()
+        .map(|(_, expr)| {
+            let clipped = frame.clone().lazy().select([expr.clone()]).collect()?;
+            let changed = frame.column("x")?.not_equal(&clipped.get_columns()[0])?;
+            Ok(changed.sum().unwrap_or(0) as f64 / frame.height().max(1) as f64)
+        })
+        .collect::<PolarsResult<Vec<f64>>>()?;
+    
+    let mut results = time_expressions(&frame, variants, opts)?;
+    for (result, clamped_fraction) in results.iter_mut().zip(clamped_fractions) {
+        result.clamped_fraction = Some(clamped_fraction);
+    }
+    Ok(results)
+}
+
+/// Times `abs`, `sign`, `ceil`, `floor`, `round(2)` and `sqrt` on `x`, each in its own
+/// `with_columns` call. Operations taking more than twice as long as `abs` are flagged,
+/// as they are likely not vectorized.
+fn benchmark_unary_numeric(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?;
+    let variants = vec![
+        ("unary_abs".to_string(), col("x").abs()),
+        ("unary_sign".to_string(), col("x").sign()),
+        ("unary_ceil".to_string(), col("x").ceil()),
+        ("unary_floor".to_string(), col("x").floor()),
+        ("unary_round".to_string(), col("x").round(2)),
+        ("unary_sqrt".to_string(), col("x").sqrt()),
+    ];
+    
+    let mut results = time_expressions(&frame, variants, opts)?;
+    for result in &mut results {
+        result.rows_per_second = rows_per_second(frame.height(), Duration::from_micros(result.duration_us));
+    }
+    
+    let abs_us = results[0].duration_us;
+    for result in &results[1..] {
+        if result.duration_us > abs_us.saturating_mul(2) {
+            println!("  ⚠️ {} took {}µs vs {}µs for unary_abs", result.operation, result.duration_us, abs_us);
+        }
+    }
+    
+    Ok(results)
+}
+
+/// Times AND, OR, NOT and XOR on two precomputed masks (`x > 0.3` and `y < 0.7`),
+/// then the same AND written as one expression over `x` and `y`, which has to
+/// evaluate both comparisons as well.
+fn benchmark_boolean_mask_operations(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(
+        &df.clone().select([
+            col("x"),
+            col("y"),
+            col("x").gt(lit(0.3)).alias("mask_a"),
+            col("y").lt(lit(0.7)).alias("mask_b"),
+        ]),
+        opts,
+    )?;
+    
+    let variants = vec![
+        ("mask_and".to_string(), col("mask_a").and(col("mask_b"))),
+        ("mask_or".to_string(), col("mask_a").or(col("mask_b"))),
+        ("mask_not".to_string(), col("mask_a").not()),
+        ("mask_xor".to_string(), col("mask_a").xor(col("mask_b"))),
+        ("mask_and_fused".to_string(), col("x").gt(lit(0.3)).and(col("y").lt(lit(0.7)))),
+    ];
+    time_expressions(&frame, variants, opts)
+}
+
+/// Splits `"<name>-<id>"` strings on `-` into a `name_list` column and records the
+/// total number of tokens. Returns the token lists for `benchmark_list_to_string`.
+fn benchmark_string_split_to_list(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<(BenchmarkResult, DataFrame)> {
+    let frame = collect_capped(
+        &df.clone().select([(col("name") + lit("-") + col("id").cast(DataType::Utf8)).alias("name")]),
+        opts,
+    )?;
+    
+    let (tokens, duration, memory_used) = measure(|| {
+        frame.lazy()
+            .select([col("name").str().split("-").alias("name_list")])
+            .collect()
+    })?;
+    let total_tokens = tokens.column("name_list")?.list()?.lst_lengths().sum().map(|n| n as usize);
+    
+    let result = BenchmarkResult {
+        total_tokens,
+        ..BenchmarkResult::new("str_split", duration, memory_used, Some(tokens.height()))
+    }
+    .with_output(&tokens, opts)?;
+    Ok((result, tokens))
+}
+
+/// Joins the token lists from `benchmark_string_split_to_list` back into strings.
+fn benchmark_list_to_string(tokens: &DataFrame, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    let total_tokens = tokens.column("name_list")?.list()?.lst_lengths().sum().map(|n| n as usize);
+    let (result, duration, memory_used) = measure(|| {
+        tokens.clone()
+            .lazy()
+            .select([col("name_list").list().join("-").alias("name")])
+            .collect()
+    })?;
+    
+    BenchmarkResult {
+        total_tokens,
+        ..BenchmarkResult::new("list_join", duration, memory_used, Some(result.height()))
+    }
+    .with_output(&result, opts)
+}
+
+/// Runs the same query as `filter → groupby → agg` and as `groupby → agg → filter`,
+/// each with predicate pushdown on and off. The predicate is on the group key, so all
+/// four return the same groups, and with pushdown the optimizer can move the late
+/// filter ahead of the group by.
+fn benchmark_filter_groupby_order(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let predicate = col("name").lt(lit("M"));
+    let aggs = [col("x").sum().alias("x_sum"), col("y").mean().alias("y_mean")];
+    
+    let filter_first = df.clone().filter(predicate.clone()).groupby([col("name")]).agg(aggs.clone());
+    let groupby_first = df.clone().groupby([col("name")]).agg(aggs).filter(predicate);
+    
+    let mut results = Vec::new();
+    for (order, query) in [("filter_then_groupby", filter_first), ("groupby_then_filter", groupby_first)] {
+        for (optimizer, pushdown) in [("pushdown", true), ("no_pushdown", false)] {
+            let (result, duration, memory_used) =
+                measure(|| query.clone().with_predicate_pushdown(pushdown).collect())?;
+            results.push(
+                BenchmarkResult::new(&format!("{}_{}", order, optimizer), duration, memory_used, Some(result.height()))
+                    .with_output(&result, opts)?,
+            );
+        }
+    }
+    
+    Ok(results)
+}
+
+/// Times two window expressions over `name`: each row's share of the overall `x`
+/// total taken by its group, and the rank of `x` within its group.
+fn benchmark_nested_groupby(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name"), col("x")]), opts)?;
+    let variants = [
+        ("window_group_share", col("x").sum().over([col("name")]) / col("x").sum()),
+        ("window_group_rank", col("x").rank(RankOptions::default(), None).over([col("name")])),
+    ];
+    let expr_nodes: Vec<usize> = variants.iter().map(|(_, expr)| expr.into_iter().count()).collect();
+    
+    let mut results = time_expressions(&frame, variants, opts)?;
+    for (result, nodes) in results.iter_mut().zip(expr_nodes) {
+        result.expr_nodes = Some(nodes);
+    }
+    Ok(results)
+}
+
+/// Writes the scanned dataset to `cache_path` unless it is already there, then times
+/// `benchmark_read` on the remote and local copies. Returns the results together with
+/// the size of the cached file. The cache holds whatever the scan returns, so a run
+/// with `--limit-rows` caches only those rows.
+fn benchmark_read_remote_vs_local(
+    remote: &str,
+    cache_path: &Path,
+    scan_args: &ScanArgsParquet,
+    opts: &BenchmarkOptions,
+) -> PolarsResult<(Vec<BenchmarkResult>, u64)> {
+    if !cache_path.exists() {
+        println!("  ⬇️ Caching {} to {}", remote, cache_path.display());
+        let mut frame = LazyFrame::scan_parquet(remote, scan_args.clone())?.collect()?;
+        if let Some(parent) = cache_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write next to the target first so an interrupted download is not mistaken for a cache.
+        let partial = cache_path.with_extension("partial");
+        ParquetWriter::new(std::fs::File::create(&partial)?).finish(&mut frame)?;
+        std::fs::rename(&partial, cache_path)?;
+    }
+    let local = cache_path
+        .to_str()
+        .ok_or_else(|| PolarsError::ComputeError("local cache path is not valid UTF-8".into()))?;
+    
+    let mut remote_result = benchmark_read(remote, scan_args, opts)?;
+    remote_result.operation = "read_parquet_remote".to_string();
+    let mut local_result = benchmark_read(local, scan_args, opts)?;
+    local_result.operation = "read_parquet_local".to_string();
+    local_result.remote_vs_local_speedup = speedup(
+        Duration::from_micros(remote_result.duration_us),
+        Duration::from_micros(local_result.duration_us),
+    );
+    
+    let bytes_read = std::fs::metadata(cache_path)?.len();
+    Ok((vec![remote_result, local_result], bytes_read))
+}
+
+/// For each chunk size, times slicing the collected frame into pieces of that many
+/// rows and stacking the pieces back into one frame with `vstack`.
+fn benchmark_into_chunks(df: &LazyFrame, chunk_sizes: &[u64], opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    
+    let mut results = Vec::new();
+    for &chunk_size in chunk_sizes {
+        let chunk_size = chunk_size as usize;
+        let n_chunks = frame.height().div_ceil(chunk_size);
+        
+        let (chunks, duration, memory_used) = measure(|| {
+            Ok((0..n_chunks)
+                .map(|i| frame.slice((i * chunk_size) as i64, chunk_size))
+                .collect::<Vec<_>>())
+        })?;
+        results.push(BenchmarkResult {
+            num_partitions: Some(chunks.len()),
+            ..BenchmarkResult::new(&format!("into_chunks_{}", chunk_size), duration, memory_used, Some(frame.height()))
+        });
+        
+        let (rebuilt, duration, memory_used) = measure(|| {
+            let mut chunks = chunks.into_iter();
+            let mut rebuilt = chunks.next().unwrap_or_else(|| frame.clear());
+            for chunk in chunks {
+                rebuilt.vstack_mut(&chunk)?;
+            }
+            Ok(rebuilt)
+        })?;
+        results.push(
+            BenchmarkResult {
+                num_partitions: Some(n_chunks),
+                ..BenchmarkResult::new(&format!("vstack_chunks_{}", chunk_size), duration, memory_used, Some(rebuilt.height()))
+            }
+            .with_output(&rebuilt, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Times `frame_equal` and `frame_equal_missing` against a clone of the collected
+/// frame (equal) and against a copy whose last `x` value was changed (not equal).
+fn benchmark_frame_equality(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    let same = frame.clone();
+    
+    let last = frame.height().saturating_sub(1);
+    let mut x: Float64Chunked = frame
+        .column("x")?
+        .f64()?
+        .into_iter()
+        .enumerate()
+        .map(|(i, v)| if i == last { Some(v.unwrap_or(0.0) + 1.0) } else { v })
+        .collect();
+    x.rename("x");
+    let mut modified = frame.clone();
+    modified.with_column(x.into_series())?;
+    
+    let comparisons = [("clone", &same), ("modified", &modified)];
+    let mut results = Vec::new();
+    for (label, other) in comparisons {
+        let (equal, duration, memory_used) = measure(|| Ok(frame.frame_equal(other)))?;
+        results.push(BenchmarkResult {
+            result_bool: Some(equal),
+            ..BenchmarkResult::new(&format!("frame_equal_{}", label), duration, memory_used, Some(frame.height()))
+        });
+        
+        let (equal, duration, memory_used) = measure(|| Ok(frame.frame_equal_missing(other)))?;
+        results.push(BenchmarkResult {
+            result_bool: Some(equal),
+            ..BenchmarkResult::new(&format!("frame_equal_missing_{}", label), duration, memory_used, Some(frame.height()))
+        });
+    }
+    
+    Ok(results)
+}
+
+/// Times selecting every column of the collected frame with the `col("*")` wildcard
+/// against naming each column, with a plain `DataFrame::clone` as the baseline.
+fn benchmark_select_all_columns(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    let explicit: Vec<Expr> = frame.get_column_names().iter().map(|name| col(name)).collect();
+    
+    let (cloned, duration, memory_used) = measure(|| Ok(frame.clone()))?;
+    let mut results = vec![
+        BenchmarkResult::new("frame_clone", duration, memory_used, Some(cloned.height())).with_output(&cloned, opts)?,
+    ];
+    
+    for (operation, exprs) in [("select_wildcard", vec![col("*")]), ("select_explicit_columns", explicit)] {
+        let (result, duration, memory_used) = measure(|| frame.clone().lazy().select(exprs).collect())?;
+        results.push(
+            BenchmarkResult::new(operation, duration, memory_used, Some(result.height())).with_output(&result, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Builds a `Float64` Series of `len` values backed by a buffer with room for `capacity`.
+fn series_with_capacity(name: &str, len: usize, capacity: usize) -> Series {
+    let mut values = Vec::with_capacity(capacity.max(len));
+    values.extend((0..len).map(|i| i as f64));
+    Float64Chunked::from_vec(name, values).into_series()
+}
+
+/// For several sizes, times `Series::extend` in place, once with a full buffer that
+/// must reallocate and once with the capacity already reserved, against appending
+/// into a new Series and rechunking it.
+fn benchmark_extend(opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut results = Vec::new();
+    for len in [10_000, 100_000, 1_000_000] {
+        let other = series_with_capacity("b", len, len);
+        
+        for pre_allocated in [false, true] {
+            let (capacity, suffix) = if pre_allocated { (2 * len, "_preallocated") } else { (len, "") };
+            let mut series = series_with_capacity("a", len, capacity);
+            let (_, duration, memory_used) = measure(|| series.extend(&other).map(|_| ()))?;
+            results.push(
+                BenchmarkResult {
+                    capacity_pre_allocated: Some(pre_allocated),
+                    ..BenchmarkResult::new(&format!("series_extend_{}{}", len, suffix), duration, memory_used, Some(series.len()))
+                }
+                .with_output(&DataFrame::new(vec![series])?, opts)?,
+            );
+        }
+        
+        let series = series_with_capacity("a", len, len);
+        let (concatenated, duration, memory_used) = measure(|| {
+            let mut concatenated = series.clone();
+            concatenated.append(&other)?;
+            Ok(concatenated.rechunk())
+        })?;
+        results.push(
+            BenchmarkResult::new(&format!("series_concat_{}", len), duration, memory_used, Some(concatenated.len()))
+                .with_output(&DataFrame::new(vec![concatenated])?, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Counts the fields of `schema` that are missing from `expected` or have a
+/// different dtype there, plus the expected fields `schema` lacks.
+fn schema_mismatches(schema: &Schema, expected: &Schema) -> usize {
+    let wrong = schema.iter().filter(|(name, dtype)| expected.get(name) != Some(*dtype)).count();
+    let missing = expected.iter_names().filter(|name| schema.get(name).is_none()).count();
+    wrong + missing
+}
+
+/// Validates the collected frame against an expected schema that narrows the 64-bit
+/// numeric columns to 32 bits, then times casting the frame to that schema.
+fn benchmark_schema_validation(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    let expected: Schema = frame
+        .schema()
+        .iter()
+        .map(|(name, dtype)| {
+            let target = match dtype {
+                DataType::Float64 => DataType::Float32,
+                DataType::Int64 => DataType::Int32,
+                other => other.clone(),
+            };
+            Field::new(name, target)
+        })
+        .collect();
+    
+    let (mismatches, duration, memory_used) = measure(|| Ok(schema_mismatches(&frame.schema(), &expected)))?;
+    let validate_result = BenchmarkResult {
+        schema_mismatch_count: Some(mismatches),
+        ..BenchmarkResult::new("schema_validation", duration, memory_used, Some(frame.height()))
+    };
+    
+    let casts: Vec<Expr> = expected.iter().map(|(name, dtype)| col(name).cast(dtype.clone())).collect();
+    let (cast, duration, memory_used) = measure(|| frame.clone().lazy().select(casts).collect())?;
+    let cast_result = BenchmarkResult {
+        schema_mismatch_count: Some(schema_mismatches(&cast.schema(), &expected)),
+        ..BenchmarkResult::new("schema_cast", duration, memory_used, Some(cast.height()))
+    }
+    .with_output(&cast, opts)?;
+    
+    Ok(vec![validate_result, cast_result])
+}
+
+/// Times copying `x` out of Polars as a `Vec<Option<f64>>`, as a `Vec<f64>` through
+/// the no-null iterator, and as a raw Arrow array. The no-null variant is skipped
+/// when `x` has nulls, since it would read masked-out values.
+fn benchmark_series_to_vec(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?;
+    let x = frame.column("x")?;
+    let nulls_present = Some(x.null_count() > 0);
+    let values = x.f64()?;
+    
+    let mut results = Vec::new();
+    let (vec, duration, memory_used) = measure(|| Ok(values.to_vec()))?;
+    results.push(BenchmarkResult {
+        nulls_present,
+        ..BenchmarkResult::new("series_to_vec_option", duration, memory_used, Some(vec.len()))
+    });
+    
+    if x.null_count() == 0 {
+        let (vec, duration, memory_used) = measure(|| Ok(values.into_no_null_iter().collect::<Vec<f64>>()))?;
+        results.push(BenchmarkResult {
+            nulls_present,
+            ..BenchmarkResult::new("series_to_vec_no_null", duration, memory_used, Some(vec.len()))
+        });
+    }
+    
+    let (array, duration, memory_used) = measure(|| Ok(x.to_arrow(0)))?;
+    results.push(BenchmarkResult {
+        nulls_present,
+        ..BenchmarkResult::new("series_to_arrow", duration, memory_used, Some(array.len()))
+    });
+    
+    Ok(results)
+}
+
+/// Writes the collected frame to temporary CSV and NDJSON files, then times building
+/// a lazy scan of each and resolving its schema while inferring dtypes from 100 rows,
+/// 10,000 rows and the whole file. The inferred schema is compared with the Parquet one.
+fn benchmark_lazy_frame_schema_inference(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut frame = collect_capped(df, opts)?;
+    let parquet_schema = frame.schema();
+    
+    let dir = TempDirGuard::new("polars-benchmark-inference")?;
+    let csv_path = dir.0.join("data.csv");
+    let json_path = dir.0.join("data.ndjson");
+    CsvWriter::new(std::fs::File::create(&csv_path)?).finish(&mut frame)?;
+    JsonWriter::new(std::fs::File::create(&json_path)?)
+        .with_json_format(JsonFormat::JsonLines)
+        .finish(&mut frame)?;
+    
+    let mut results = Vec::new();
+    for (label, infer_len) in [("100", Some(100)), ("10000", Some(10_000)), ("full", None)] {
+        let (csv_schema, duration, memory_used) = measure(|| {
+            LazyCsvReader::new(&csv_path)
+                .with_infer_schema_length(infer_len)
+                .finish()?
+                .schema()
+        })?;
+        results.push(BenchmarkResult {
+            inferred_schema_mismatches: Some(schema_mismatches(&csv_schema, &parquet_schema)),
+            ..BenchmarkResult::new(&format!("infer_schema_csv_{}", label), duration, memory_used, Some(frame.height()))
+        });
+        
+        let (json_schema, duration, memory_used) = measure(|| {
+            LazyJsonLineReader::new(&json_path)
+                .with_infer_schema_length(infer_len)
+                .finish()?
+                .schema()
+        })?;
+        results.push(BenchmarkResult {
+            inferred_schema_mismatches: Some(schema_mismatches(&json_schema, &parquet_schema)),
+            ..BenchmarkResult::new(&format!("infer_schema_json_{}", label), duration, memory_used, Some(frame.height()))
+        });
+    }
+    
+    Ok(results)
+}
+
+/// With `x` nulled on every other row, times `DataFrame::null_count`, counting nulls
+/// and non-nulls of `x` with expressions, dropping rows with nulls, and filling
+/// nulls with the column mean.
+fn benchmark_null_count(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = null_every_nth_x(collect_capped(&df.clone().select([col("id"), col("x"), col("y")]), opts)?, 2)?;
+    
+    let (counts, duration, memory_used) = measure(|| Ok(frame.null_count()))?;
+    let total_nulls_found = counts.get_columns().iter().filter_map(|s| s.sum::<usize>()).sum();
+    let mut results = vec![
+        BenchmarkResult {
+            total_nulls_found: Some(total_nulls_found),
+            ..BenchmarkResult::new("null_count", duration, memory_used, Some(frame.height()))
+        }
+        .with_output(&counts, opts)?,
+    ];
+    
+    let variants = [("is_null_sum", col("x").is_null().sum()), ("is_not_null_sum", col("x").is_not_null().sum())];
+    for (operation, expr) in variants {
+        let (result, duration, memory_used) = measure(|| frame.clone().lazy().select([expr]).collect())?;
+        results.push(
+            BenchmarkResult::new(operation, duration, memory_used, Some(frame.height())).with_output(&result, opts)?,
+        );
+    }
+    
+    let (dropped, duration, memory_used) = measure(|| frame.drop_nulls::<String>(None))?;
+    results.push(
+        BenchmarkResult::new("drop_nulls", duration, memory_used, Some(dropped.height())).with_output(&dropped, opts)?,
+    );
+    
+    let (filled, duration, memory_used) = measure(|| frame.fill_null(FillNullStrategy::Mean))?;
+    results.push(
+        BenchmarkResult::new("fill_null_mean", duration, memory_used, Some(filled.height())).with_output(&filled, opts)?,
+    );
+    
+    Ok(results)
+}
+
+/// Times `all` on an all-true column and on one whose first value is false, and
+/// `any` on an all-false column and on one whose first value is true, to show whether
+/// the reductions stop at the first deciding value.
+fn benchmark_all_any(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let is_first = col("row_nr").eq(lit(0));
+    let masks = collect_capped(&df.clone().select([col("id")]), opts)?
+        .lazy()
+        .with_row_count("row_nr", None)
+        .select([
+            col("row_nr").gt_eq(lit(0)).alias("all_true"),
+            is_first.clone().not().alias("first_false"),
+            col("row_nr").lt(lit(0)).alias("all_false"),
+            is_first.alias("first_true"),
+        ])
+        .collect()?;
+    
+    let variants = [
+        ("all_full_scan", col("all_true").all(true)),
+        ("all_early_exit", col("first_false").all(true)),
+        ("any_full_scan", col("all_false").any(true)),
+        ("any_early_exit", col("first_true").any(true)),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, expr) in variants {
+        let (result, duration, memory_used) = measure(|| masks.clone().lazy().select([expr]).collect())?;
+        let answer = result.get_columns()[0].bool()?.get(0);
+        results.push(
+            BenchmarkResult {
+                result_bool: answer,
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(masks.height()))
+            }
+            .with_output(&result, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Times `cumsum`, `cummax`, `cummin` and `cumprod` on `x`, plus an expanding mean
+/// built from `cumsum` divided by the running row count, recording each output dtype.
+fn benchmark_running_totals(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?
+        .lazy()
+        .with_row_count("row_nr", None)
+        .collect()?;
+    let variants = vec![
+        ("cumsum".to_string(), col("x").cumsum(false)),
+        ("cummax".to_string(), col("x").cummax(false)),
+        ("cummin".to_string(), col("x").cummin(false)),
+        ("cumprod".to_string(), col("x").cumprod(false)),
+        (
+            "expanding_mean".to_string(),
+            col("x").cumsum(false) / (col("row_nr") + lit(1)).cast(DataType::Float64),
+        ),
+    ];
+    
+    let mut dtypes = Vec::new();
+    for (_, expr) in &variants {
+        let schema = frame.clone().lazy().select([expr.clone().alias("result")]).schema()?;
+        dtypes.push(schema.get("result").map(|dtype| dtype.to_string()));
+    }
+    
+    let mut results = time_expressions(&frame, variants, opts)?;
+    for (result, dtype) in results.iter_mut().zip(dtypes) {
+        result.output_dtype = dtype;
+    }
+    Ok(results)
+}
+
+/// Packs `x`, `y` and `name` into a struct column, then times reading `x` back by
+/// name and by index and unnesting every field, against selecting `x` directly.
+/// `struct_round_trip` builds the struct and reads the field in one query.
+fn benchmark_struct_field_access(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x"), col("y"), col("name")]), opts)?;
+    let packed = as_struct(&[col("x"), col("y"), col("name")]).alias("s");
+    
+    let (structs, duration, memory_used) = measure(|| frame.clone().lazy().select([packed.clone()]).collect())?;
+    let mut results = vec![
+        BenchmarkResult::new("struct_create", duration, memory_used, Some(structs.height())).with_output(&structs, opts)?,
+    ];
+    
+    let (direct, direct_duration, memory_used) = measure(|| frame.clone().lazy().select([col("x")]).collect())?;
+    results.push(
+        BenchmarkResult::new("direct_column_access", direct_duration, memory_used, Some(direct.height()))
+            .with_output(&direct, opts)?,
+    );
+    
+    let accesses = [
+        ("struct_field_by_name", structs.clone().lazy().select([col("s").struct_().field_by_name("x")])),
+        ("struct_field_by_index", structs.clone().lazy().select([col("s").struct_().field_by_index(0)])),
+        ("struct_unnest", structs.clone().lazy().unnest(["s"])),
+        ("struct_round_trip", frame.clone().lazy().select([packed.struct_().field_by_name("x")])),
+    ];
+    for (operation, query) in accesses {
+        let (result, duration, memory_used) = measure(|| query.collect())?;
+        let round_trip_overhead = if operation == "struct_round_trip" {
+            speedup(duration, direct_duration)
+        } else {
+            None
+        };
+        results.push(
+            BenchmarkResult {
+                round_trip_overhead,
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(result.height()))
+            }
+            .with_output(&result, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Times computing the sort indices of `x` with `arg_sort`, then gathering the frame
+/// with them via `take`, against sorting the whole frame by `x` in one step.
+fn benchmark_arg_sort(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    
+    let (indices, duration, memory_used) = measure(|| {
+        frame.clone()
+            .lazy()
+            .select([col("x").arg_sort(SortOptions::default())])
+            .collect()
+    })?;
+    let arg_sort_result =
+        BenchmarkResult::new("arg_sort", duration, memory_used, Some(indices.height())).with_output(&indices, opts)?;
+    
+    let (taken, duration, memory_used) = measure(|| frame.take(indices.column("x")?.idx()?))?;
+    let take_result =
+        BenchmarkResult::new("take_by_arg_sort", duration, memory_used, Some(taken.height())).with_output(&taken, opts)?;
+    
+    let (sorted, duration, memory_used) = measure(|| frame.clone().lazy().sort("x", SortOptions::default()).collect())?;
+    let sort_result =
+        BenchmarkResult::new("sort_full", duration, memory_used, Some(sorted.height())).with_output(&sorted, opts)?;
+    
+    Ok(vec![arg_sort_result, take_result, sort_result])
+}
+
+/// Times `mode` on the low-cardinality `name` column and the high-cardinality `id`
+/// column. Ties return several rows, which are counted in `result_rows`.
+fn benchmark_mode(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name"), col("id")]), opts)?;
+    
+    let mut results = Vec::new();
+    for column in ["name", "id"] {
+        let (modes, duration, memory_used) = measure(|| frame.clone().lazy().select([col(column).mode()]).collect())?;
+        results.push(
+            BenchmarkResult {
+                result_rows: Some(modes.height()),
+                ..BenchmarkResult::new(&format!("mode_{}", column), duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&modes, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Times the base-2 entropy of the `name` distribution computed by hand from
+/// `value_counts` and with the `entropy` expression on the same counts, plus
+/// `entropy` on `|x|` used as weights. Each entropy is stored in `result_value`.
+fn benchmark_entropy(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name"), col("x")]), opts)?;
+    let counts = || {
+        frame.clone()
+            .lazy()
+            .select([col("name").value_counts(true, true)])
+            .unnest(["name"])
+    };
+    let p = col("counts").cast(DataType::Float64) / col("counts").sum().cast(DataType::Float64);
+    let manual = lit(0.0) - (p.clone() * p.log(2.0)).sum();
+    
+    let variants = [
+        ("entropy_value_counts_manual", counts().select([manual.alias("entropy")])),
+        ("entropy_value_counts", counts().select([col("counts").entropy(2.0, true).alias("entropy")])),
+        ("entropy_x", frame.clone().lazy().select([col("x").abs().entropy(2.0, true).alias("entropy")])),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, query) in variants {
+        let (result, duration, memory_used) = measure(|| query.collect())?;
+        let entropy = result.column("entropy")?.f64()?.get(0);
+        results.push(
+            BenchmarkResult {
+                result_value: entropy.map(|value| value.to_string()),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&result, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Times Pearson correlation, Spearman rank correlation and covariance of `x` and `y`
+/// as separate selects, then the full 2x2 Pearson matrix of `x` and `y` in one select.
+/// Each answer is stored in `result_value` to check against the Python run.
+fn benchmark_corr_cov(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x"), col("y")]), opts)?;
+    
+    let variants = [
+        ("pearson_corr", vec![pearson_corr(col("x"), col("y"), 1).alias("xy")]),
+        ("spearman_rank_corr", vec![spearman_rank_corr(col("x"), col("y"), 1, false).alias("xy")]),
+        ("cov", vec![cov(col("x"), col("y")).alias("xy")]),
+        (
+            "corr_matrix_2x2",
+            vec![
+                pearson_corr(col("x"), col("x"), 1).alias("xx"),
+                pearson_corr(col("x"), col("y"), 1).alias("xy"),
+                pearson_corr(col("y"), col("x"), 1).alias("yx"),
+                pearson_corr(col("y"), col("y"), 1).alias("yy"),
+            ],
+        ),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, exprs) in variants {
+        let (result, duration, memory_used) = measure(|| frame.clone().lazy().select(exprs.clone()).collect())?;
+        let values = result
+            .get_columns()
+            .iter()
+            .map(|column| {
+                let value = column.cast(&DataType::Float64)?.f64()?.get(0);
+                Ok(value.map_or("null".to_string(), |value| value.to_string()))
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        results.push(
+            BenchmarkResult {
+                result_value: Some(values.join(",")),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&result, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Times finding the position of the smallest and largest `x` with `arg_min` and
+/// `arg_max`, against taking the first `arg_sort` index and filtering for the minimum
+/// then keeping the first row. `min` alone is the baseline without the index work.
+/// The index (or the minimum for the baseline) is stored in `result_value`.
+fn benchmark_arg_min_max(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?;
+    let lazy = || frame.clone().lazy();
+    
+    let variants = [
+        ("arg_min", lazy().select([col("x").arg_min()])),
+        ("arg_max", lazy().select([col("x").arg_max()])),
+        ("arg_sort_first", lazy().select([col("x").arg_sort(SortOptions::default()).first()])),
+        (
+            "filter_eq_min_head",
+            lazy()
+                .with_row_count("row_nr", None)
+                .filter(col("x").eq(col("x").min()))
+                .select([col("row_nr")])
+                .limit(1),
+        ),
+        ("min_baseline", lazy().select([col("x").min()])),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, query) in variants {
+        let (result, duration, memory_used) = measure(|| query.collect())?;
+        let value = result.get_columns()[0].get(0)?.to_string();
+        results.push(
+            BenchmarkResult {
+                result_value: Some(value),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&result, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Splits the sorted unique `name` values into two sets that share about half of each
+/// set's values, then times their intersection, union, difference and symmetric
+/// difference. The size of each output set is stored in `result_rows`.
+fn benchmark_series_set_operations(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let names = collect_capped(&df.clone().select([col("name").unique().sort(false)]), opts)?;
+    let names = names.column("name")?;
+    let third = names.len() / 3;
+    let a = names.slice(0, 2 * third);
+    let b = names.slice(third as i64, names.len() - third);
+    
+    let frame_a = DataFrame::new(vec![a])?;
+    let frame_b = DataFrame::new(vec![b])?;
+    let difference = |left: &DataFrame, right: &DataFrame| {
+        left.clone()
+            .lazy()
+            .filter(col("name").is_in(lit(right.column("name")?.clone())).not())
+            .collect()
+    };
+    let union = |left: &DataFrame, right: &DataFrame| {
+        left.vstack(right)?.lazy().unique_stable(None, UniqueKeepStrategy::First).collect()
+    };
+    
+    let measured = [
+        (
+            "set_intersection",
+            measure(|| frame_a.clone().lazy().filter(col("name").is_in(lit(frame_b.column("name")?.clone()))).collect())?,
+        ),
+        ("set_union", measure(|| union(&frame_a, &frame_b))?),
+        ("set_difference", measure(|| difference(&frame_a, &frame_b))?),
+        (
+            "set_symmetric_difference",
+            measure(|| union(&difference(&frame_a, &frame_b)?, &difference(&frame_b, &frame_a)?))?,
+        ),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, (set, duration, memory_used)) in measured {
+        results.push(
+            BenchmarkResult {
+                result_rows: Some(set.height()),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(frame_a.height() + frame_b.height()))
+            }
+            .with_output(&set, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Writes up to `--max-eager-rows` rows to Parquet twice, in 16 row groups with and
+/// without column statistics, and times an `is_in` filter on `id` scanned from each.
+/// The benchmark is named for bloom filters, but Polars 0.32's `ParquetWriter` cannot
+/// write them and its reader does not consult them, so statistics are the only
+/// row-group pruning compared here and `bloom_filter_enabled` is reported as false.
+fn benchmark_scan_with_bloom_filter(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut frame = collect_capped(df, opts)?;
+    let wanted = frame.column("id")?.head(Some(3));
+    let row_group_size = (frame.height() / 16).max(1);
+    
+    let dir = TempDirGuard::new("polars-benchmark-bloom")?;
+    let mut results = Vec::new();
+    for (label, statistics) in [("with_statistics", true), ("without_statistics", false)] {
+        let path = dir.0.join(format!("{}.parquet", label));
+        ParquetWriter::new(std::fs::File::create(&path)?)
+            .with_statistics(statistics)
+            .with_row_group_size(Some(row_group_size))
+            .finish(&mut frame)?;
+        
+        let (matched, duration, memory_used) = measure(|| {
+            LazyFrame::scan_parquet(&path, ScanArgsParquet::default())?
+                .filter(col("id").is_in(lit(wanted.clone())))
+                .collect()
+        })?;
+        results.push(
+            BenchmarkResult {
+                result_rows: Some(matched.height()),
+                ..BenchmarkResult::new(&format!("scan_is_in_{}", label), duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&matched, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Adds ten copies of `x` as `x1`..`x10`, once through ten chained `with_columns`
+/// calls and once through a single `with_columns` holding all ten expressions. The
+/// single call's `speedup_ratio` shows how much batching column additions saves.
+fn benchmark_expression_fusion(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    let copies = (1..=10).map(|i| col("x").alias(&format!("x{}", i))).collect::<Vec<_>>();
+    
+    let (chained, chained_duration, memory_used) = measure(|| {
+        copies
+            .iter()
+            .fold(frame.clone().lazy(), |lazy, expr| lazy.with_columns([expr.clone()]))
+            .collect()
+    })?;
+    let chained_result =
+        BenchmarkResult::new("with_columns_chained_10", chained_duration, memory_used, Some(chained.height()))
+            .with_output(&chained, opts)?;
+    
+    let (single, duration, memory_used) = measure(|| frame.clone().lazy().with_columns(copies.clone()).collect())?;
+    let single_result = BenchmarkResult {
+        speedup_ratio: speedup(chained_duration, duration),
+        ..BenchmarkResult::new("with_columns_single_10", duration, memory_used, Some(single.height()))
+    }
+    .with_output(&single, opts)?;
+    
+    Ok(vec![chained_result, single_result])
+}
+
+/// Nulls out `x` on every fifth row, then filters on `x > 0.5` with the nulls turned
+/// into `false` by `fill_null`, left for `filter` to drop, and excluded up front with
+/// `is_not_null`. All three should keep the same rows, which `result_rows` records.
+fn benchmark_null_filter_semantics(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let sparse = null_every_nth_x(collect_capped(&df.clone().select([col("x"), col("y")]), opts)?, 5)?;
+    
+    let variants = [
+        ("filter_null_fill_false", col("x").gt(lit(0.5)).fill_null(lit(false))),
+        ("filter_null_implicit", col("x").gt(lit(0.5))),
+        ("filter_null_is_not_null", col("x").is_not_null().and(col("x").gt(lit(0.5)))),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, predicate) in variants {
+        let (filtered, duration, memory_used) = measure(|| sparse.clone().lazy().filter(predicate).collect())?;
+        results.push(
+            BenchmarkResult {
+                result_rows: Some(filtered.height()),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(sparse.height()))
+            }
+            .with_output(&filtered, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Writes up to `--max-eager-rows` rows sorted by `id` to Parquet in 16 row groups, so
+/// each group's min/max statistics cover a narrow `id` range, then times a full scan
+/// against a scan filtered on `id` below the median. Row groups starting at or above
+/// the median can be skipped from their statistics alone; the ones that cannot are
+/// counted from the sorted layout in `estimated_row_groups_scanned`.
+fn benchmark_scan_with_statistics_min_max(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut frame = collect_capped(df, opts)?.sort(["id"], false, false)?;
+    let row_group_size = (frame.height() / 16).max(1);
+    let ids = frame.column("id")?.cast(&DataType::Int64)?;
+    let ids = ids.i64()?;
+    let median = ids.get(frame.height() / 2).unwrap_or_default();
+    let row_group_mins = (0..frame.height()).step_by(row_group_size).map(|start| ids.get(start));
+    let row_groups_scanned = row_group_mins.filter(|min| min.is_none_or(|min| min < median)).count();
+    
+    let dir = TempDirGuard::new("polars-benchmark-statistics")?;
+    let path = dir.0.join("sorted.parquet");
+    ParquetWriter::new(std::fs::File::create(&path)?)
+        .with_statistics(true)
+        .with_row_group_size(Some(row_group_size))
+        .finish(&mut frame)?;
+    let total_row_groups = frame.height().div_ceil(row_group_size);
+    
+    let scans = [
+        ("scan_statistics_full", None, total_row_groups),
+        ("scan_statistics_range", Some(col("id").lt(lit(median))), row_groups_scanned),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, predicate, row_groups) in scans {
+        let (scanned, duration, memory_used) = measure(|| {
+            let lazy = LazyFrame::scan_parquet(&path, ScanArgsParquet::default())?;
+            match predicate {
+                Some(predicate) => lazy.filter(predicate).collect(),
+                None => lazy.collect(),
+            }
+        })?;
+        results.push(
+            BenchmarkResult {
+                result_rows: Some(scanned.height()),
+                estimated_row_groups_scanned: Some(row_groups),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&scanned, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Times element-wise work on the grouped `x_list` column through `list().eval` (a sum
+/// and a doubling) against the native `list().max()`, each selected on its own.
+/// `total_list_elements` is the number of elements every variant walks over. Only the
+/// new column is selected, so `x_list` is neither copied nor part of the checksum.
+fn benchmark_list_arithmetic(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let (lists, total_list_elements) = list_column_frame(df)?;
+    
+    let variants = [
+        ("list_eval_sum", col("x_list").list().eval(col("").sum(), false)),
+        ("list_eval_times_two", col("x_list").list().eval(col("") * lit(2.0), false)),
+        ("list_max_native", col("x_list").list().max()),
+    ];
+    
+    let mut results = time_selections(&lists, variants, opts)?;
+    for result in &mut results {
+        result.total_list_elements = Some(total_list_elements);
+    }
+    Ok(results)
+}
+
+/// Times `first`, `last` and `take` of a single position of `x` within each `name`
+/// group. Polars 0.32 has no `get`, so `take` with a literal index stands in for it.
+/// Positions are 0, 1000, half and the last index of the smallest group, clamped to
+/// that group so no lookup is out of bounds; the one used is in `result_value`.
+/// `result_bool` records whether the output differs from `first`, which it should
+/// for every position but 0.
+fn benchmark_first_last_nth(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name"), col("x")]), opts)?;
+    let group_sizes = frame.clone().lazy().groupby([col("name")]).agg([col("x").count()]).collect()?;
+    let smallest = group_sizes.column("x")?.cast(&DataType::UInt64)?.u64()?.min().unwrap_or(0) as usize;
+    let last = smallest.saturating_sub(1);
+    
+    let mut variants = vec![
+        ("agg_first".to_string(), col("x").first(), None),
+        ("agg_last".to_string(), col("x").last(), None),
+    ];
+    for (label, position) in [("0", 0), ("1000", 1000), ("half", smallest / 2), ("last", last)] {
+        let position = position.min(last);
+        variants.push((format!("agg_take_{}", label), col("x").take(lit(position as IdxSize)), Some(position)));
+    }
+    
+    let mut first = None;
+    let mut results = Vec::new();
+    for (operation, expr, position) in variants {
+        let (picked, duration, memory_used) = measure(|| {
+            frame.clone()
+                .lazy()
+                .groupby_stable([col("name")])
+                .agg([expr])
+                .collect()
+        })?;
+        let first = first.get_or_insert_with(|| picked.clone());
+        results.push(
+            BenchmarkResult {
+                result_value: position.map(|position| position.to_string()),
+                result_bool: Some(!picked.frame_equal_missing(first)),
+                ..BenchmarkResult::new(&operation, duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&picked, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Tags `timestamp` as UTC, then times adding a one day duration, `offset_by` one
+/// calendar month, truncating to the week, rounding to the day and converting to
+/// America/New_York. The conversion only swaps the time zone on the dtype, as the
+/// stored UTC values do not change, so it should cost next to nothing.
+fn benchmark_date_arithmetic(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(
+        &df.clone().select([col("timestamp").dt().replace_time_zone(Some("UTC".to_string()), None)]),
+        opts,
+    )?;
+    let truncate = TruncateOptions {
+        every: "1w".to_string(),
+        offset: "0ns".to_string(),
+        use_earliest: None,
+    };
+    
+    let variants = vec![
+        ("date_add_1d".to_string(), col("timestamp") + lit(chrono::Duration::days(1))),
+        ("date_offset_by_1mo".to_string(), col("timestamp").dt().offset_by(polars::prelude::Duration::parse("1mo"))),
+        ("date_truncate_1w".to_string(), col("timestamp").dt().truncate(truncate)),
+        ("date_round_1d".to_string(), col("timestamp").dt().round("1d", "0ns")),
+        (
+            "date_convert_time_zone".to_string(),
+            col("timestamp").dt().convert_time_zone("America/New_York".to_string()),
+        ),
+    ];
+    time_expressions(&frame, variants, opts)
+}
+
+/// Times three queries that use `timestamp` as a time axis: daily means of `x` and
+/// `y` over `timestamp` truncated to the day (`timeseries_resample`), a
+/// `groupby_dynamic` per `name` with 3-month windows starting every month, after
+/// sorting by `timestamp` (`timeseries_rolling_groupby`), and a filter down to the
+/// calendar year 2000, the first year of the dataset, so runs with `--limit-rows` keep
+/// their rows (`timeseries_date_filter`). Polars 0.32 has no `is_between`, so the
+/// filter is a pair of comparisons.
+fn benchmark_timeseries(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    const FILTER_YEAR: i32 = 2000;
+    let daily = TruncateOptions {
+        every: "1d".to_string(),
+        offset: "0ns".to_string(),
+        use_earliest: None,
+    };
+    let (resampled, duration, memory_used) = measure(|| {
+        df.clone()
+            .groupby([col("timestamp").dt().truncate(daily).alias("day")])
+            .agg([col("x").mean().alias("x_mean"), col("y").mean().alias("y_mean"), col("x").count().alias("count")])
+            .collect()
+    })?;
+    let resample_result = BenchmarkResult::new("timeseries_resample", duration, memory_used, Some(resampled.height()))
+        .with_output(&resampled, opts)?;
+    
+    let windows = DynamicGroupOptions {
+        every: polars::prelude::Duration::parse("1mo"),
+        period: polars::prelude::Duration::parse("3mo"),
+        offset: polars::prelude::Duration::parse("0ns"),
+        ..Default::default()
+    };
+    let (rolling, duration, memory_used) = measure(|| {
+        df.clone()
+            .sort("timestamp", SortOptions::default())
+            .groupby_dynamic(col("timestamp"), [col("name")], windows)
+            .agg([col("x").mean().alias("x_mean"), col("y").sum().alias("y_sum")])
+            .collect()
+    })?;
+    let rolling_result =
+        BenchmarkResult::new("timeseries_rolling_groupby", duration, memory_used, Some(rolling.height()))
+            .with_output(&rolling, opts)?;
+    
+    let year_start = |year: i32| {
+        chrono::NaiveDate::from_ymd_opt(year, 1, 1).and_then(|date| date.and_hms_opt(0, 0, 0)).expect("valid date")
+    };
+    let in_year = col("timestamp")
+        .gt_eq(lit(year_start(FILTER_YEAR)))
+        .and(col("timestamp").lt(lit(year_start(FILTER_YEAR + 1))));
+    let (filtered, duration, memory_used) = measure(|| df.clone().filter(in_year).collect())?;
+    let filter_result = BenchmarkResult::new("timeseries_date_filter", duration, memory_used, Some(filtered.height()))
+        .with_output(&filtered, opts)?;
+    
+    Ok(vec![resample_result, rolling_result, filter_result])
+}
+
+/// Times bitwise AND, OR and XOR of `id` against constant masks, which Polars runs
+/// as integer bit operations when `and`/`or`/`xor` get integer operands. Polars 0.32
+/// has no bit shift expression, so `shift_and_fill` (moving rows down by two) is
+/// timed alongside as the closest element-wise shift.
+fn benchmark_bitwise_operations(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("id").cast(DataType::Int64)]), opts)?;
+    
+    let variants = vec![
+        ("bitwise_and".to_string(), col("id").and(lit(0xFFFF_i64))),
+        ("bitwise_or".to_string(), col("id").or(lit(0x0001_i64))),
+        ("bitwise_xor".to_string(), col("id").xor(lit(0xAAAA_i64))),
+        ("shift_and_fill".to_string(), col("id").shift_and_fill(2, lit(0_i64))),
+    ];
+    time_expressions(&frame, variants, opts)
+}
+
+/// Builds a `name_unicode` column by appending kanji and an emoji to every `name`,
+/// then times character counts, byte lengths and a literal `contains` on it and on
+/// the ASCII `name` column. `is_unicode_heavy` tells the two sets of results apart.
+fn benchmark_encode_decode_utf8(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut frame = collect_capped(&df.clone().select([col("name")]), opts)?;
+    let mut unicode: Utf8Chunked = frame
+        .column("name")?
+        .utf8()?
+        .into_iter()
+        .map(|name| name.map(|name| format!("{}・東京タワー🗼", name)))
+        .collect();
+    unicode.rename("name_unicode");
+    frame.with_column(unicode.into_series())?;
+    
+    let mut results = Vec::new();
+    for (column, is_unicode_heavy) in [("name", false), ("name_unicode", true)] {
+        let variants = vec![
+            (format!("utf8_n_chars_{}", column), col(column).str().n_chars()),
+            (format!("utf8_lengths_{}", column), col(column).str().lengths()),
+            (format!("utf8_contains_{}", column), col(column).str().contains_literal(lit("li"))),
+        ];
+        let mut column_results = time_expressions(&frame, variants, opts)?;
+        for result in &mut column_results {
+            result.is_unicode_heavy = Some(is_unicode_heavy);
+        }
+        results.extend(column_results);
+    }
+    
+    Ok(results)
+}
+
+/// Times the exact `n_unique` of `id` against the HyperLogLog based `approx_n_unique`.
+/// Both counts are stored in `result_value` and the approximation's relative error in
+/// `approx_error_pct`.
+fn benchmark_approx_unique(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("id")]), opts)?;
+    let count = |expr: Expr| {
+        let counted = frame.clone().lazy().select([expr.cast(DataType::UInt64).alias("count")]).collect()?;
+        let count = counted.column("count")?.u64()?.get(0).unwrap_or(0);
+        PolarsResult::Ok((counted, count))
+    };
+    
+    let ((exact_frame, exact), duration, memory_used) = measure(|| count(col("id").n_unique()))?;
+    let exact_result = BenchmarkResult {
+        result_value: Some(exact.to_string()),
+        ..BenchmarkResult::new("n_unique_exact", duration, memory_used, Some(frame.height()))
+    }
+    .with_output(&exact_frame, opts)?;
+    
+    let ((approx_frame, approx), duration, memory_used) = measure(|| count(col("id").approx_n_unique()))?;
+    let approx_result = BenchmarkResult {
+        result_value: Some(approx.to_string()),
+        approx_error_pct: (exact > 0).then(|| approx.abs_diff(exact) as f64 / exact as f64 * 100.0),
+        ..BenchmarkResult::new("n_unique_approx", duration, memory_used, Some(frame.height()))
+    }
+    .with_output(&approx_frame, opts)?;
+    
+    Ok(vec![exact_result, approx_result])
+}
+
+/// Gathers the same scattered tenth of the rows with `take` from a frame of 100
+/// stacked chunks and from its single-chunk copy. The indices come from a fixed
+/// multiplicative hash so every run gathers the same rows. `speedup_ratio` on the
+/// single-chunk result is how much rechunking up front saved.
+fn benchmark_take_rechunk_vs_gather(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let multi_chunk = fragmented_frame(df, 100, 10_000)?;
+    let mut single_chunk = multi_chunk.clone();
+    single_chunk.as_single_chunk_par();
+    
+    let n_rows = multi_chunk.height() as u64;
+    let indices = IdxCa::from_vec(
+        "idx",
+        (0..n_rows / 10).map(|i| (i.wrapping_mul(2_654_435_761) % n_rows.max(1)) as IdxSize).collect(),
+    );
+    
+    let (taken, multi_duration, memory_used) = measure(|| multi_chunk.take(&indices))?;
+    let multi_result = BenchmarkResult {
+        n_chunks_input: Some(multi_chunk.n_chunks()),
+        ..BenchmarkResult::new("take_multi_chunk", multi_duration, memory_used, Some(taken.height()))
+    }
+    .with_output(&taken, opts)?;
+    
+    let (taken, duration, memory_used) = measure(|| single_chunk.take(&indices))?;
+    let single_result = BenchmarkResult {
+        n_chunks_input: Some(single_chunk.n_chunks()),
+        speedup_ratio: speedup(multi_duration, duration),
+        ..BenchmarkResult::new("take_single_chunk", duration, memory_used, Some(taken.height()))
+    }
+    .with_output(&taken, opts)?;
+    
+    Ok(vec![multi_result, single_result])
+}
+
+/// Casts `x` scaled by a million (so the integer casts keep six digits) to `Int32`,
+/// `Int64`, `Float32` and `Float64` before timing, then times `sum`, `mean`, `max` and
+/// `min` on each column as a separate select.
+fn benchmark_numeric_dtype_arithmetic(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let dtypes = [
+        ("i32", DataType::Int32),
+        ("i64", DataType::Int64),
+        ("f32", DataType::Float32),
+        ("f64", DataType::Float64),
+    ];
+    let casts = dtypes.iter().map(|(label, dtype)| (col("x") * lit(1_000_000.0)).cast(dtype.clone()).alias(label));
+    let frame = collect_capped(&df.clone().select(casts.collect::<Vec<_>>()), opts)?;
+    
+    let mut results = Vec::new();
+    for (label, _) in &dtypes {
+        let aggregations = [
+            ("sum", col(label).sum()),
+            ("mean", col(label).mean()),
+            ("max", col(label).max()),
+            ("min", col(label).min()),
+        ];
+        for (aggregation, expr) in aggregations {
+            let (result, duration, memory_used) = measure(|| frame.clone().lazy().select([expr]).collect())?;
+            let operation = format!("numeric_{}_{}", aggregation, label);
+            results.push(
+                BenchmarkResult::new(&operation, duration, memory_used, Some(frame.height())).with_output(&result, opts)?,
+            );
+        }
+    }
+    
+    Ok(results)
+}
+
+/// Groups by a struct key built from `name` and `id % 10`, against grouping by the
+/// same two values as separate key columns, to isolate the cost of hashing a struct.
+fn benchmark_struct_groupby(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let bucket = (col("id") % lit(10)).cast(DataType::Int32).alias("bucket");
+    let frame = collect_capped(&df.clone().select([col("name"), bucket, col("x")]), opts)?;
+    let keyed = frame.clone().lazy().with_column(as_struct(&[col("name"), col("bucket")]).alias("key")).collect()?;
+    let unique_struct_keys = Some(keyed.column("key")?.n_unique()?);
+    
+    let (grouped, duration, memory_used) =
+        measure(|| keyed.clone().lazy().groupby([col("key")]).agg([col("x").sum()]).collect())?;
+    let struct_result = BenchmarkResult {
+        unique_struct_keys,
+        ..BenchmarkResult::new("groupby_struct_key", duration, memory_used, Some(grouped.height()))
+    }
+    .with_output(&grouped.unnest(["key"])?, opts)?;
+    
+    let (grouped, duration, memory_used) =
+        measure(|| frame.clone().lazy().groupby([col("name"), col("bucket")]).agg([col("x").sum()]).collect())?;
+    let composite_result = BenchmarkResult::new("groupby_composite_key", duration, memory_used, Some(grouped.height()))
+        .with_output(&grouped, opts)?;
+    
+    Ok(vec![struct_result, composite_result])
+}
+
+/// Runs each of the benchmarks in `names` in its own child process, started with this
+/// run's arguments, and kills any child still running after `timeout`. A killed
+/// benchmark gets a single result with `timed_out` set and the timeout as its
+/// duration. Running the benchmarks on threads instead would leave a hung S3 read
+/// running in this process, and the registered closures are not `Send` anyway.
+fn run_with_timeouts(
+    names: &[String],
+    timeout: Duration,
+    dataset_info: &RefCell<DatasetInfo>,
+) -> PolarsResult<Vec<BenchmarkResult>> {
+    let dir = TempDirGuard::new("polars-benchmark-timeouts")?;
+    let mut results = Vec::new();
+    for name in names {
+        println!("🔄 Running {} benchmark (timeout {}s)...", name, timeout.as_secs());
+        let output = dir.0.join(format!("{}.json", name));
+        let mut child = std::process::Command::new(std::env::current_exe()?)
+            .args(std::env::args_os().skip(1))
+            .arg("--output")
+            .arg(&output)
+            .arg("--child-benchmarks")
+            .arg(name)
+            .stdout(std::process::Stdio::null())
+            .spawn()?;
+        
+        let start = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break Some(status);
+            }
+            if start.elapsed() >= timeout {
+                child.kill()?;
+                child.wait()?;
+                break None;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        };
+        
+        match status {
+            Some(status) if status.success() => {
+                let suite: BenchmarkSuite = serde_json::from_slice(&std::fs::read(&output)?).map_err(|e| {
+                    PolarsError::ComputeError(format!("could not read {} benchmark results: {}", name, e).into())
+                })?;
+                let mut info = dataset_info.borrow_mut();
+                info.avg_string_len_bytes = info.avg_string_len_bytes.or(suite.dataset_info.avg_string_len_bytes);
+                info.estimated_row_width_bytes =
+                    info.estimated_row_width_bytes.or(suite.dataset_info.estimated_row_width_bytes);
+                info.bytes_read = info.bytes_read.or(suite.dataset_info.bytes_read);
+                info.bloom_filter_enabled = info.bloom_filter_enabled.or(suite.dataset_info.bloom_filter_enabled);
+                results.extend(suite.results);
+            }
+            Some(status) => {
+                return Err(PolarsError::ComputeError(format!("{} benchmark failed: {}", name, status).into()));
+            }
+            None => {
+                println!("⚠️  {} did not finish within {}s and was killed", name, timeout.as_secs());
+                results.push(BenchmarkResult { timed_out: true, ..BenchmarkResult::new(name, timeout, 0, None) });
+            }
+        }
+    }
+    Ok(results)
+}
+
+/// Runs this binary again as a child process with this run's arguments and the
+/// environment variables in `envs`, and reads back the suite it writes to `output`.
+/// An empty `benchmarks` runs every registered benchmark. Settings such as
+/// `POLARS_MAX_THREADS` go through the child's environment because they are read
+/// when Polars starts, and setting them in this process would race with the
+/// threads already reading the environment.
+fn run_child_suite(benchmarks: &[String], envs: &[(&str, &OsStr)], output: &Path) -> PolarsResult<BenchmarkSuite> {
+    let mut child = std::process::Command::new(std::env::current_exe()?);
+    child
+        .args(std::env::args_os().skip(1))
+        .arg("--output")
+        .arg(output)
+        .arg("--child-benchmarks")
+        .arg(benchmarks.join(","))
+        .envs(envs.iter().copied())
+        .stdout(std::process::Stdio::null());
+    
+    let status = child.status()?;
+    if !status.success() {
+        return Err(PolarsError::ComputeError(format!("child benchmark run failed: {}", status).into()));
+    }
+    serde_json::from_slice(&std::fs::read(output)?)
+        .map_err(|e| PolarsError::ComputeError(format!("could not read child benchmark results: {}", e).into()))
+}
+
+/// Reruns the aggregation, group by and sort benchmarks with the default Polars
+/// thread pool, one thread and half the default threads. `POLARS_MAX_THREADS` is
+/// only read when the pool starts, so setting it in this process would have no
+/// effect; each setting instead runs this binary again as a child process with the
+/// variable set, and the child's pool size is recorded in `thread_count`.
+fn benchmark_thread_scaling() -> PolarsResult<Vec<BenchmarkResult>> {
+    let default_threads = polars_core::POOL.current_num_threads();
+    let settings = [("default", None), ("1", Some(1)), ("half", Some((default_threads / 2).max(1)))];
+    let dir = TempDirGuard::new("polars-benchmark-threads")?;
+    
+    let benchmarks = ["aggregation".to_string(), "group_by".to_string(), "sort".to_string()];
+    let mut results = Vec::new();
+    for (label, threads) in settings {
+        let output = dir.0.join(format!("threads_{}.json", label));
+        let threads_var = threads.map(|threads| threads.to_string());
+        let envs: Vec<(&str, &OsStr)> =
+            threads_var.iter().map(|threads| ("POLARS_MAX_THREADS", threads.as_ref())).collect();
+        let suite = run_child_suite(&benchmarks, &envs, &output)
+            .map_err(|e| PolarsError::ComputeError(format!("thread scaling run with {} threads: {}", label, e).into()))?;
+        
+        for result in suite.results {
+            results.push(BenchmarkResult {
+                operation: format!("{}_threads_{}", result.operation, label),
+                thread_count: Some(suite.system_info.polars_threads),
+                ..result
+            });
+        }
+    }
+    
+    Ok(results)
+}
+
+/// For 10, 100, 1000 and 10000 distinct keys, builds a `key` column cycling through
+/// that many `<name>_<i>` strings and times a group by on it as `Utf8` and after
+/// casting to `Categorical` (outside the timing). `speedup_ratio` on the categorical
+/// result is its gain over the string key. Categorical keys are cast back to strings
+/// for the checksum, so both variants checksum the same.
+fn benchmark_categorical_encoding(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name"), col("x")]), opts)?;
+    let names = frame.column("name")?.unique_stable()?;
+    let names: Vec<&str> = names.utf8()?.into_no_null_iter().collect();
+    
+    let mut results = Vec::new();
+    for cardinality in [10, 100, 1_000, 10_000] {
+        let keys: Vec<String> = (0..cardinality)
+            .map(|i| format!("{}_{}", names.get(i % names.len().max(1)).unwrap_or(&""), i))
+            .collect();
+        let key = Utf8Chunked::from_iter_values("key", (0..frame.height()).map(|row| keys[row % cardinality].as_str()));
+        let utf8 = DataFrame::new(vec![key.into_series(), frame.column("x")?.clone()])?;
+        let categorical = utf8.clone().lazy().with_column(col("key").cast(DataType::Categorical(None))).collect()?;
+        
+        let group = |keyed: &DataFrame| keyed.clone().lazy().groupby([col("key")]).agg([col("x").sum()]).collect();
+        let (grouped, utf8_duration, memory_used) = measure(|| group(&utf8))?;
+        results.push(
+            BenchmarkResult {
+                cardinality: Some(cardinality),
+                ..BenchmarkResult::new(&format!("groupby_utf8_{}", cardinality), utf8_duration, memory_used, Some(grouped.height()))
+            }
+            .with_output(&grouped, opts)?,
+        );
+        
+        let (grouped, duration, memory_used) = measure(|| group(&categorical))?;
+        let operation = format!("groupby_categorical_{}", cardinality);
+        results.push(
+            BenchmarkResult {
+                cardinality: Some(cardinality),
+                speedup_ratio: speedup(utf8_duration, duration),
+                ..BenchmarkResult::new(&operation, duration, memory_used, Some(grouped.height()))
+            }
+            .with_output(&grouped.lazy().with_column(col("key").cast(DataType::Utf8)).collect()?, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Filters `x > 0.5` on 100,000 rows split into 1, 10, 100 and 1000 equal chunks,
+/// and on a rechunked copy of each as the baseline. `speedup_ratio` on the rechunked
+/// result is how much calling `rechunk` before the filter would have saved.
+fn benchmark_rechunk_overhead_in_filter(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let filter = |frame: &DataFrame| frame.clone().lazy().filter(col("x").gt(lit(0.5))).collect();
+    
+    let mut results = Vec::new();
+    for n_chunks in [1, 10, 100, 1_000] {
+        let fragmented = fragmented_frame(df, n_chunks, 100_000 / n_chunks)?;
+        let mut rechunked = fragmented.clone();
+        rechunked.as_single_chunk_par();
+        
+        let (filtered, fragmented_duration, memory_used) = measure(|| filter(&fragmented))?;
+        let operation = format!("filter_chunks_{}", n_chunks);
+        results.push(
+            BenchmarkResult {
+                n_chunks_input: Some(fragmented.n_chunks()),
+                ..BenchmarkResult::new(&operation, fragmented_duration, memory_used, Some(fragmented.height()))
+            }
+            .with_output(&filtered, opts)?,
+        );
+        
+        let (filtered, duration, memory_used) = measure(|| filter(&rechunked))?;
+        let operation = format!("filter_chunks_{}_rechunked", n_chunks);
+        results.push(
+            BenchmarkResult {
+                n_chunks_input: Some(rechunked.n_chunks()),
+                speedup_ratio: speedup(fragmented_duration, duration),
+                ..BenchmarkResult::new(&operation, duration, memory_used, Some(rechunked.height()))
+            }
+            .with_output(&filtered, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Sorts `x` as loaded (random order), sorted with every twentieth value replaced by
+/// the unsorted one (95% sorted) and sorted descending, each with and without
+/// multithreading.
+fn benchmark_parallel_sort_distributions(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?;
+    let random: Vec<f64> = frame.column("x")?.f64()?.into_no_null_iter().collect();
+    let mut sorted = random.clone();
+    sorted.sort_by(f64::total_cmp);
+    let mut nearly_sorted = sorted.clone();
+    for i in (0..nearly_sorted.len()).step_by(20) {
+        nearly_sorted[i] = random[i];
+    }
+    sorted.reverse();
+    
+    let mut results = Vec::new();
+    for (distribution, values) in [("random", random), ("nearly_sorted", nearly_sorted), ("reverse_sorted", sorted)] {
+        let input = DataFrame::new(vec![Series::new("x", values)])?;
+        for (label, multithreaded) in [("multithreaded", true), ("single_threaded", false)] {
+            let options = SortOptions { multithreaded, ..Default::default() };
+            let (sorted, duration, memory_used) = measure(|| input.clone().lazy().sort("x", options).collect())?;
+            let operation = format!("sort_{}_{}", distribution, label);
+            results.push(
+                BenchmarkResult {
+                    sort_input_distribution: Some(distribution.to_string()),
+                    ..BenchmarkResult::new(&operation, duration, memory_used, Some(sorted.height()))
+                }
+                .with_output(&sorted, opts)?,
+            );
+        }
+    }
+    
+    Ok(results)
+}
+
+/// Chains 5, 10, 25 and 50 operations alternating a filter on `x` and a
+/// `with_columns` shifting `y`, then times `explain(true)` (optimization only) and
+/// `collect` (optimization plus execution) on each plan.
+fn benchmark_pipeline_depth(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x"), col("y")]), opts)?;
+    
+    let mut results = Vec::new();
+    for depth in [5, 10, 25, 50] {
+        let pipeline = (0..depth).fold(frame.clone().lazy(), |lazy, i| {
+            if i % 2 == 0 {
+                lazy.filter(col("x").gt(lit(-1.0 + i as f64 * 0.01)))
+            } else {
+                lazy.with_columns([(col("y") + lit(1.0)).alias("y")])
+            }
+        });
+        
+        let (_, duration, memory_used) = measure(|| pipeline.explain(true))?;
+        let operation = format!("pipeline_explain_{}", depth);
+        results.push(BenchmarkResult::new(&operation, duration, memory_used, Some(frame.height())));
+        
+        let (collected, duration, memory_used) = measure(|| pipeline.clone().collect())?;
+        results.push(
+            BenchmarkResult::new(&format!("pipeline_collect_{}", depth), duration, memory_used, Some(collected.height()))
+                .with_output(&collected, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Writes up to `--max-eager-rows` rows to Parquet in 16 row groups, then times
+/// reading only the footer against a `limit(0)` scan, which shows whether the
+/// optimizer also avoids reading data. `ParquetReader::get_metadata` is private in
+/// Polars 0.32, so the footer is read with arrow2's `read_metadata`, which it wraps.
+fn benchmark_read_metadata_only(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut frame = collect_capped(df, opts)?;
+    let dir = TempDirGuard::new("polars-benchmark-metadata")?;
+    let path = dir.0.join("data.parquet");
+    ParquetWriter::new(std::fs::File::create(&path)?)
+        .with_row_group_size(Some((frame.height() / 16).max(1)))
+        .finish(&mut frame)?;
+    let file_size_bytes = Some(std::fs::metadata(&path)?.len());
+    
+    let (metadata, duration, memory_used) = measure(|| {
+        let mut file = std::fs::File::open(&path)?;
+        Ok(polars::export::arrow::io::parquet::read::read_metadata(&mut file)?)
+    })?;
+    let metadata_result = BenchmarkResult {
+        num_row_groups: Some(metadata.row_groups.len()),
+        total_rows_in_metadata: Some(metadata.num_rows),
+        file_size_bytes,
+        ..BenchmarkResult::new("read_parquet_metadata", duration, memory_used, Some(metadata.num_rows))
+    };
+    
+    let (empty, duration, memory_used) =
+        measure(|| LazyFrame::scan_parquet(&path, ScanArgsParquet::default())?.limit(0).collect())?;
+    let limit_result = BenchmarkResult {
+        file_size_bytes,
+        ..BenchmarkResult::new("scan_parquet_limit_0", duration, memory_used, Some(empty.height()))
+    };
+    
+    Ok(vec![metadata_result, limit_result])
+}
+
+/// Times 30-row rolling standard deviation and variance of `x` against a rolling mean
+/// of the same window, plus the exponentially weighted `ewm_std` and `ewm_var` with
+/// default options. The rolling results carry their `window_size`.
+fn benchmark_rolling_std_var(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    const WINDOW: usize = 30;
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?;
+    let window = || RollingOptions {
+        window_size: polars::prelude::Duration::new(WINDOW as i64),
+        min_periods: WINDOW,
+        ..Default::default()
+    };
+    
+    let variants = vec![
+        ("rolling_mean".to_string(), col("x").rolling_mean(window())),
+        ("rolling_std".to_string(), col("x").rolling_std(window())),
+        ("rolling_var".to_string(), col("x").rolling_var(window())),
+        ("ewm_std".to_string(), col("x").ewm_std(EWMOptions::default())),
+        ("ewm_var".to_string(), col("x").ewm_var(EWMOptions::default())),
+    ];
+    let mut results = time_expressions(&frame, variants, opts)?;
+    for result in results.iter_mut().filter(|result| result.operation.starts_with("rolling_")) {
+        result.window_size = Some(WINDOW);
+    }
+    Ok(results)
+}
+
+/// Orders `x` within each `name` by aggregating the sorted values into lists and
+/// exploding them back, against sorting the frame by `name` and `x` directly.
+/// `speedup_ratio` on the direct sort is how much faster it was than the round trip
+/// (below 1 means slower).
+fn benchmark_list_explode_roundtrip(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name"), col("x")]), opts)?;
+    
+    let (exploded, roundtrip_duration, memory_used) = measure(|| {
+        frame.clone()
+            .lazy()
+            .groupby([col("name")])
+            .agg([col("x").sort(false)])
+            .explode([col("x")])
+            .collect()
+    })?;
+    let roundtrip_result =
+        BenchmarkResult::new("list_explode_roundtrip", roundtrip_duration, memory_used, Some(exploded.height()))
+            .with_output(&exploded, opts)?;
+    
+    let (sorted, duration, memory_used) = measure(|| {
+        frame.clone()
+            .lazy()
+            .sort_by_exprs([col("name"), col("x")], [false, false], false, false)
+            .collect()
+    })?;
+    let sort_result = BenchmarkResult {
+        speedup_ratio: speedup(roundtrip_duration, duration),
+        ..BenchmarkResult::new("sort_by_name_x", duration, memory_used, Some(sorted.height()))
+    }
+    .with_output(&sorted, opts)?;
+    
+    Ok(vec![roundtrip_result, sort_result])
+}
+
+/// Splits `df` into `k` train/test pairs for cross-validation. Rows are shuffled by
+/// ordering their indices on a hash seeded with `seed`, then dealt round-robin into
+/// `k` folds; pair `i` holds out fold `i` as the test frame and trains on the rest.
+fn kfold_split(df: &DataFrame, k: usize, seed: u64) -> PolarsResult<Vec<(DataFrame, DataFrame)>> {
+    let hasher = polars::export::ahash::RandomState::with_seeds(seed, 1, 2, 3);
+    let mut shuffled: Vec<IdxSize> = (0..df.height() as IdxSize).collect();
+    shuffled.sort_by_cached_key(|&row| hasher.hash_one(row));
+    
+    let mut folds = vec![Vec::new(); k];
+    for (position, row) in shuffled.into_iter().enumerate() {
+        folds[position % k].push(row);
+    }
+    
+    (0..k)
+        .map(|test_fold| {
+            let train_rows: Vec<IdxSize> = folds
+                .iter()
+                .enumerate()
+                .filter(|(fold, _)| *fold != test_fold)
+                .flat_map(|(_, rows)| rows.iter().copied())
+                .collect();
+            let train = df.take(&IdxCa::from_vec("", train_rows))?;
+            let test = df.take(&IdxCa::from_vec("", folds[test_fold].clone()))?;
+            Ok((train, test))
+        })
+        .collect()
+}
+
+/// Times generating every train/test pair of a 5-fold and a 10-fold split of up to
+/// `--max-eager-rows` rows with `kfold_split`.
+fn benchmark_cross_validation_split(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    
+    let mut results = Vec::new();
+    for k in [5, 10] {
+        let (folds, duration, memory_used) = measure(|| kfold_split(&frame, k, 42))?;
+        let operation = format!("kfold_split_{}", k);
+        results.push(BenchmarkResult {
+            folds_generated: Some(folds.len()),
+            total_rows_split: Some(folds.iter().map(|(_, test)| test.height()).sum()),
+            ..BenchmarkResult::new(&operation, duration, memory_used, Some(frame.height()))
+        });
+    }
+    
+    Ok(results)
+}
+
+/// Assembles frames of 1, 10, 50 and 100 columns with `DataFrame::new`, each column
+/// its own copy of `x`, then repeats the 100-column frame with `new_no_checks`,
+/// which skips the length and duplicate name validation. `speedup_ratio` on that
+/// result is the validation overhead.
+fn benchmark_horizontal_concat_frames(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?;
+    let values: Vec<f64> = frame.column("x")?.f64()?.into_no_null_iter().collect();
+    let columns = |width: usize| -> Vec<Series> {
+        (0..width).map(|i| Series::new(&format!("x_{}", i), values.clone())).collect()
+    };
+    
+    let mut results = Vec::new();
+    let mut checked_duration = Duration::ZERO;
+    for width in [1, 10, 50, 100] {
+        let series = columns(width);
+        let (assembled, duration, memory_used) = measure(|| DataFrame::new(series))?;
+        checked_duration = duration;
+        let operation = format!("hconcat_series_{}", width);
+        results.push(
+            BenchmarkResult::new(&operation, duration, memory_used, Some(assembled.height()))
+                .with_output(&assembled, opts)?,
+        );
+    }
+    
+    let series = columns(100);
+    let (assembled, duration, memory_used) = measure(|| Ok(DataFrame::new_no_checks(series)))?;
+    results.push(
+        BenchmarkResult {
+            speedup_ratio: speedup(checked_duration, duration),
+            ..BenchmarkResult::new("hconcat_series_100_no_checks", duration, memory_used, Some(assembled.height()))
+        }
+        .with_output(&assembled, opts)?,
+    );
+    
+    Ok(results)
+}
+
+/// Melts frames of `id` plus 5, 20 and 100 value columns (aliased copies of `x`)
+/// into long format. Each value column becomes one output row per input row, so
+/// `expansion_ratio` should equal the number of value columns.
+fn benchmark_dataframe_melt(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut results = Vec::new();
+    for width in [5, 20, 100] {
+        let value_columns: Vec<String> = (0..width).map(|i| format!("x_{}", i)).collect();
+        let wide = collect_capped(
+            &df.clone().select(
+                std::iter::once(col("id"))
+                    .chain(value_columns.iter().map(|name| col("x").alias(name)))
+                    .collect::<Vec<_>>(),
+            ),
+            opts,
+        )?;
+        
+        let (long, duration, memory_used) = measure(|| wide.melt(["id"], &value_columns))?;
+        let operation = format!("melt_{}_columns", width);
+        results.push(
+            BenchmarkResult {
+                output_row_count: Some(long.height()),
+                expansion_ratio: (wide.height() > 0).then(|| long.height() as f64 / wide.height() as f64),
+                ..BenchmarkResult::new(&operation, duration, memory_used, Some(wide.height()))
+            }
+            .with_output(&long, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Builds Series of 100K, 1M and 10M zeros with `Series::new`, which copies the
+/// values, and `ChunkedArray::from_vec`, which takes ownership of the vector, then a
+/// frame of ten `from_vec` columns. The vectors are allocated before timing starts,
+/// and are all zeros, so there is no output worth checksumming.
+fn benchmark_series_creation() -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut results = Vec::new();
+    for len in [100_000, 1_000_000, 10_000_000] {
+        let values = vec![0.0f64; len];
+        
+        let (series, duration, memory_used) = measure(|| Ok(Series::new("x", &values)))?;
+        results.push(BenchmarkResult {
+            rows_per_second: rows_per_second(series.len(), duration),
+            ..BenchmarkResult::new(&format!("series_new_{}", len), duration, memory_used, Some(series.len()))
+        });
+        
+        let owned = values.clone();
+        let (series, duration, memory_used) = measure(|| Ok(Float64Chunked::from_vec("x", owned).into_series()))?;
+        results.push(BenchmarkResult {
+            rows_per_second: rows_per_second(series.len(), duration),
+            ..BenchmarkResult::new(&format!("series_from_vec_{}", len), duration, memory_used, Some(series.len()))
+        });
+        
+        let columns: Vec<Vec<f64>> = (0..10).map(|_| values.clone()).collect();
+        let (frame, duration, memory_used) = measure(|| {
+            DataFrame::new(
+                columns
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, column)| Float64Chunked::from_vec(&format!("x_{}", i), column).into_series())
+                    .collect(),
+            )
+        })?;
+        results.push(BenchmarkResult {
+            rows_per_second: rows_per_second(frame.height(), duration),
+            ..BenchmarkResult::new(&format!("dataframe_from_vecs_{}", len), duration, memory_used, Some(frame.height()))
+        });
+    }
+    
+    Ok(results)
+}
+
+/// Runs the complex query's filter, group by and sort as three separate `collect`
+/// calls, each stage starting from the previous stage's materialized output, against
+/// the single fused `complex_query` collect, which never materializes the filtered
+/// and grouped frames in between. `speedup_ratio` on the fused result is what
+/// chaining cost.
+fn benchmark_chained_vs_fused(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let (chained, chained_duration, memory_used) = measure(|| {
+        let filtered = df.clone().filter(col("x").gt(lit(0.0)).and(col("y").lt(lit(1.0)))).collect()?;
+        let grouped = filtered
+            .lazy()
+            .groupby([col("name")])
+            .agg([
+                col("x").sum().alias("x_sum"),
+                col("y").mean().alias("y_mean"),
+                (col("x") * col("y")).sum().alias("xy_sum"),
+            ])
+            .collect()?;
+        grouped.lazy().sort("x_sum", SortOptions { descending: true, ..Default::default() }).collect()
+    })?;
+    let chained_result =
+        BenchmarkResult::new("complex_query_chained", chained_duration, memory_used, Some(chained.height()))
+            .with_output(&chained, opts)?;
+    
+    let (fused, duration, memory_used) = measure(|| complex_query(df).collect())?;
+    let fused_result = BenchmarkResult {
+        speedup_ratio: speedup(chained_duration, duration),
+        ..BenchmarkResult::new("complex_query_fused", duration, memory_used, Some(fused.height()))
+    }
+    .with_output(&fused, opts)?;
+    
+    Ok(vec![chained_result, fused_result])
+}
+
+/// Times stripping whitespace from either or both ends of `name` and padding it to
+/// 20 characters at the start or end. The names are wrapped in two spaces on each
+/// side first so the strips have something to remove. Polars 0.32 calls the pads
+/// `rjust` (pad start) and `ljust` (pad end).
+fn benchmark_string_normalization(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(
+        &df.clone().select([(lit("  ") + col("name") + lit("  ")).alias("name")]),
+        opts,
+    )?;
+    
+    let variants = vec![
+        ("string_lstrip".to_string(), col("name").str().lstrip(None)),
+        ("string_rstrip".to_string(), col("name").str().rstrip(None)),
+        ("string_strip".to_string(), col("name").str().strip(None)),
+        ("string_pad_start".to_string(), col("name").str().rjust(20, ' ')),
+        ("string_pad_end".to_string(), col("name").str().ljust(20, ' ')),
+    ];
+    time_expressions(&frame, variants, opts)
+}
+
+/// Packs `x` and `y` into an `inner` struct inside a `nested` struct that also holds
+/// `name`, then times reading `x` through both levels and unnesting both levels,
+/// against selecting `x` from the flat frame. `speedup_ratio` on the nested results
+/// is relative to the flat access, so below 1 is the cost of nesting.
+fn benchmark_nested_struct(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x"), col("y"), col("name")]), opts)?;
+    let inner = as_struct(&[col("x"), col("y")]).alias("inner");
+    let packed = as_struct(&[inner, col("name").alias("outer_name")]).alias("nested");
+    let nested = frame.clone().lazy().select([packed]).collect()?;
+    
+    let (flat, flat_duration, memory_used) = measure(|| frame.clone().lazy().select([col("x")]).collect())?;
+    let mut results = vec![
+        BenchmarkResult {
+            nesting_depth: Some(0),
+            ..BenchmarkResult::new("flat_column_access", flat_duration, memory_used, Some(flat.height()))
+        }
+        .with_output(&flat, opts)?,
+    ];
+    
+    let accesses = [
+        (
+            "nested_field_access",
+            nested.clone().lazy().select([col("nested").struct_().field_by_name("inner").struct_().field_by_name("x")]),
+        ),
+        ("nested_unnest", nested.clone().lazy().unnest(["nested"]).unnest(["inner"])),
+    ];
+    for (operation, query) in accesses {
+        let (result, duration, memory_used) = measure(|| query.collect())?;
+        results.push(
+            BenchmarkResult {
+                nesting_depth: Some(2),
+                speedup_ratio: speedup(flat_duration, duration),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(result.height()))
+            }
+            .with_output(&result, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// `path` as a string for the Polars scan functions, which do not take a `Path`.
+fn utf8_path(path: &Path) -> PolarsResult<String> {
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| PolarsError::ComputeError("temporary path is not valid UTF-8".into()))
+}
+
+/// Writes up to `--max-eager-rows` rows once as a single Parquet file and once split
+/// into 100 files of consecutive rows, then reads both back with the read benchmark,
+/// the split copy through a glob. The extra `partitioned_vs_monolithic_speedup` entry is not a timing of its own;
+/// its `speedup_ratio` is how many times faster the 100 files were read.
+fn benchmark_partitioned_scan(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    const NUM_FILES: usize = 100;
+    let mut frame = collect_capped(df, opts)?;
+    let dir = TempDirGuard::new("polars-benchmark-partitioned-scan")?;
+    
+    let monolithic = dir.0.join("data.parquet");
+    ParquetWriter::new(std::fs::File::create(&monolithic)?).finish(&mut frame)?;
+    
+    let parts = dir.0.join("parts");
+    std::fs::create_dir(&parts)?;
+    let rows_per_file = frame.height().div_ceil(NUM_FILES).max(1);
+    let mut part_bytes = Vec::new();
+    for i in 0..NUM_FILES {
+        let path = parts.join(format!("part-{:05}.parquet", i));
+        let mut part = frame.slice((i * rows_per_file) as i64, rows_per_file);
+        ParquetWriter::new(std::fs::File::create(&path)?).finish(&mut part)?;
+        part_bytes.push(std::fs::metadata(&path)?.len());
+    }
+    
+    let monolithic_result = BenchmarkResult {
+        operation: "read_parquet_monolithic".to_string(),
+        num_files: Some(1),
+        avg_file_size_bytes: Some(std::fs::metadata(&monolithic)?.len()),
+        ..benchmark_read(&utf8_path(&monolithic)?, &ScanArgsParquet::default(), opts)?
+    };
+    let partitioned_result = BenchmarkResult {
+        operation: "read_parquet_partitioned".to_string(),
+        num_files: Some(NUM_FILES),
+        avg_file_size_bytes: Some(part_bytes.iter().sum::<u64>() / NUM_FILES as u64),
+        ..benchmark_read(&utf8_path(&parts.join("*.parquet"))?, &ScanArgsParquet::default(), opts)?
+    };
+    let comparison = BenchmarkResult {
+        speedup_ratio: speedup(
+            Duration::from_micros(monolithic_result.duration_us),
+            Duration::from_micros(partitioned_result.duration_us),
+        ),
+        ..BenchmarkResult::new("partitioned_vs_monolithic_speedup", Duration::ZERO, 0, None)
+    };
+    
+    Ok(vec![monolithic_result, partitioned_result, comparison])
+}
+
+/// Runs the group by benchmark query on the default in-memory engine and on the
+/// streaming engine, recording the peak resident set size of each run to show what
+/// aggregating the scan in batches saves. `speedup_ratio` on the streaming result is
+/// relative to the in-memory run, so below 1 is the throughput cost of streaming.
+fn benchmark_streaming_groupby(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let query = || {
+        df.clone().groupby([col("name")]).agg([
+            col("x").sum().alias("x_sum"),
+            col("y").mean().alias("y_mean"),
+            col("id").count().alias("count"),
+        ])
+    };
+    
+    let mut results = Vec::new();
+    let mut in_memory_duration = Duration::ZERO;
+    for streaming in [false, true] {
+        let sampler = PeakMemorySampler::start();
+        let (grouped, duration, memory_used) = measure(|| query().with_streaming(streaming).collect())?;
+        let peak_memory_mb = Some(sampler.stop());
+        
+        let result = if streaming {
+            BenchmarkResult {
+                peak_memory_mb,
+                speedup_ratio: speedup(in_memory_duration, duration),
+                ..BenchmarkResult::new("group_by_streaming", duration, memory_used, Some(grouped.height()))
+            }
+        } else {
+            in_memory_duration = duration;
+            BenchmarkResult {
+                peak_memory_mb,
+                ..BenchmarkResult::new("group_by_in_memory", duration, memory_used, Some(grouped.height()))
+            }
+        };
+        results.push(result.with_output(&grouped, opts)?);
+    }
+    
+    Ok(results)
+}
+
+/// Selects `x` where `x > 0.5` and `y` elsewhere, once with `Series::zip_with` on a
+/// precomputed mask and once as a `when/then/otherwise` expression that also
+/// evaluates the mask. `speedup_ratio` on the expression is relative to `zip_with`.
+fn benchmark_zip(df: &LazyFrame, check_asm: bool, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x"), col("y")]), opts)?;
+    let x = frame.column("x")?;
+    let mask = x.gt(0.5)?;
+    
+    let (zipped, zip_duration, memory_used) = measure(|| x.zip_with(&mask, frame.column("y")?))?;
+    let zipped = DataFrame::new(vec![zipped])?;
+    let zip_result = BenchmarkResult {
+        zip_vectorized: if check_asm { zip_kernel_vectorized() } else { None },
+        ..BenchmarkResult::new("zip_with", zip_duration, memory_used, Some(zipped.height()))
+    }
+    .with_output(&zipped, opts)?;
+    
+    let (selected, duration, memory_used) = measure(|| {
+        frame.clone()
+            .lazy()
+            .select([when(col("x").gt(lit(0.5))).then(col("x")).otherwise(col("y"))])
+            .collect()
+    })?;
+    let when_result = BenchmarkResult {
+        speedup_ratio: speedup(zip_duration, duration),
+        ..BenchmarkResult::new("zip_when_then_otherwise", duration, memory_used, Some(selected.height()))
+    }
+    .with_output(&selected, opts)?;
+    
+    Ok(vec![zip_result, when_result])
+}
+
+/// Disassembles this binary with `objdump` and reports whether any function named
+/// after the zip kernel (`zip_with`, or arrow's `if_then_else` that it calls) uses
+/// packed blend or bitwise select instructions. `None` when `objdump` is missing or
+/// the binary has no symbols, as release builds are stripped.
+fn zip_kernel_vectorized() -> Option<bool> {
+    use std::io::BufRead;
+    
+    let exe = std::env::current_exe().ok()?;
+    let mut objdump = std::process::Command::new("objdump")
+        .args(["--disassemble", "--demangle", "--no-show-raw-insn"])
+        .arg(&exe)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| eprintln!("⚠️  --check-asm: could not run objdump: {}", e))
+        .ok()?;
+    let stdout = objdump.stdout.take()?;
+    
+    let packed_select = ["blendv", "pand", "pandn", "por", "andpd", "andnpd", "orpd", "andps", "andnps", "orps"];
+    let (mut in_kernel, mut kernel_found, mut vectorized) = (false, false, false);
+    for line in std::io::BufReader::new(stdout).lines() {
+        let Ok(line) = line else { break };
+        if line.ends_with(">:") {
+            // A function header such as `0000000000123450 <symbol>:`
+            in_kernel = line.contains("zip_with") || line.contains("if_then_else");
+            kernel_found |= in_kernel;
+        } else if in_kernel {
+            let mnemonic = line.split('\t').nth(1).unwrap_or("").split_whitespace().next().unwrap_or("");
+            let mnemonic = mnemonic.strip_prefix('v').unwrap_or(mnemonic);
+            vectorized |= packed_select.iter().any(|packed| mnemonic.starts_with(packed));
+        }
+    }
+    
+    objdump.wait().ok()?.success().then_some(())?;
+    if !kernel_found {
+        eprintln!("⚠️  --check-asm: no zip kernel symbols in {}", exe.display());
+        return None;
+    }
+    Some(vectorized)
+}
+
+/// Simulates data arriving from PyArrow by exporting frames of 3, 20 and 100 columns
+/// (copies of `x`, `y` and `id`) to Arrow arrays, then timing rebuilding a
+/// `DataFrame` from those arrays, which is what the Arrow C data interface bridge
+/// does on the Rust side. Any growth beyond the column count is per-column overhead.
+fn benchmark_from_arrow(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut results = Vec::new();
+    for width in [3, 20, 100] {
+        let columns: Vec<Expr> = (0..width).map(|i| col(["x", "y", "id"][i % 3]).alias(&format!("c_{}", i))).collect();
+        let mut frame = collect_capped(&df.clone().select(columns), opts)?;
+        frame.as_single_chunk_par();
+        let names: Vec<String> = frame.get_column_names().iter().map(|name| name.to_string()).collect();
+        let arrays: Vec<ArrayRef> = frame.iter_chunks().flat_map(|chunk| chunk.into_arrays()).collect();
+        
+        let (rebuilt, duration, memory_used) = measure(|| {
+            let series = names
+                .iter()
+                .zip(&arrays)
+                .map(|(name, array)| Series::try_from((name.as_str(), array.clone())))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            DataFrame::new(series)
+        })?;
+        let operation = format!("from_arrow_{}_columns", width);
+        results.push(
+            BenchmarkResult {
+                columns_in_frame: Some(rebuilt.width()),
+                bytes_transferred: Some(frame.estimated_size() as u64),
+                ..BenchmarkResult::new(&operation, duration, memory_used, Some(rebuilt.height()))
+            }
+            .with_output(&rebuilt, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Collects the first and the last 100, 10000 and 100000 rows of the scan, against
+/// collecting all of it, up to `--max-eager-rows`. `speedup_ratio` is relative to the
+/// full collect; `head` is expected to stop reading early, while `tail` has to find
+/// the end of the data.
+fn benchmark_tail_and_head(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let (full, full_duration, memory_used) = measure(|| collect_capped(df, opts))?;
+    let mut results = vec![BenchmarkResult::new("collect_full_scan", full_duration, memory_used, Some(full.height()))];
+    drop(full);
+    
+    for n in [100, 10_000, 100_000] {
+        // `limit` replaces the scan's `n_rows`, so keep `head` within `--limit-rows`.
+        let head_rows = opts.row_limit.map_or(n, |limit| n.min(limit as IdxSize));
+        for (operation, query) in [("head", df.clone().limit(head_rows)), ("tail", df.clone().tail(n))] {
+            let (rows, duration, memory_used) = measure(|| query.collect())?;
+            let speedup_ratio = speedup(full_duration, duration);
+            results.push(
+                BenchmarkResult {
+                    speedup_ratio,
+                    full_scan: speedup_ratio.map(|ratio| ratio <= 2.0),
+                    ..BenchmarkResult::new(&format!("{}_{}", operation, n), duration, memory_used, Some(rows.height()))
+                }
+                .with_output(&rows, opts)?,
+            );
+        }
+    }
+    
+    Ok(results)
+}
+
+/// Casts every numeric column (`id`, `x` and `y`) to `Float32` in one `with_columns`,
+/// against casting them all to `Float64` as the baseline, and records the frame size
+/// before and after. `speedup_ratio` on the `Float32` cast is relative to `Float64`.
+fn benchmark_schema_cast_all_columns(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    let numeric: Vec<String> = frame
+        .get_columns()
+        .iter()
+        .filter(|s| s.dtype().is_numeric())
+        .map(|s| s.name().to_string())
+        .collect();
+    let input_size_bytes = Some(frame.estimated_size() as u64);
+    
+    let mut results = Vec::new();
+    let mut float64_duration = Duration::ZERO;
+    for (operation, dtype) in [("cast_all_float64", DataType::Float64), ("cast_all_float32", DataType::Float32)] {
+        let casts: Vec<Expr> = numeric.iter().map(|name| col(name).cast(dtype.clone())).collect();
+        let (cast, duration, memory_used) = measure(|| frame.clone().lazy().with_columns(casts).collect())?;
+        let speedup_ratio = if dtype == DataType::Float64 {
+            float64_duration = duration;
+            None
+        } else {
+            speedup(float64_duration, duration)
+        };
+        results.push(
+            BenchmarkResult {
+                columns_cast: Some(numeric.len()),
+                input_size_bytes,
+                output_size_bytes: Some(cast.estimated_size() as u64),
+                speedup_ratio,
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(cast.height()))
+            }
+            .with_output(&cast, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Sums ten copies of `x` with one `cols([...]).sum()` expression and with ten
+/// separate `col(...).sum()` expressions, timing `explain(true)` (planning only) and
+/// `collect` for each. The two differ only in how the plan is written, so the
+/// planning timings are where a gap would show.
+fn benchmark_cols_vs_separate(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let names: Vec<String> = (0..10).map(|i| format!("col{}", i)).collect();
+    let copies: Vec<Expr> = names.iter().map(|name| col("x").alias(name)).collect();
+    let frame = collect_capped(&df.clone().select(copies), opts)?;
+    let separate: Vec<Expr> = names.iter().map(|name| col(name).sum().alias(&format!("{}_sum", name))).collect();
+    
+    let queries = [
+        ("cols_sum", frame.clone().lazy().select([cols(&names).sum()])),
+        ("separate_col_sum", frame.clone().lazy().select(separate)),
+    ];
+    let mut results = Vec::new();
+    for (operation, query) in queries {
+        let (_, duration, memory_used) = measure(|| query.explain(true))?;
+        results.push(BenchmarkResult::new(&format!("{}_plan", operation), duration, memory_used, Some(frame.height())));
+        
+        let (sums, duration, memory_used) = measure(|| query.collect())?;
+        results.push(
+            BenchmarkResult::new(&format!("{}_collect", operation), duration, memory_used, Some(frame.height()))
+                .with_output(&sums, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Runs the sort benchmark on the streaming engine with `POLARS_FORCE_OOC` set, which
+/// makes the sort sink spill to disk regardless of free memory. Polars 0.32 has no
+/// memory limit setting, so this stands in for a dataset larger than RAM. Spill files
+/// go under `std::env::temp_dir()`, so the sort runs in a child process, see
+/// `run_child_suite`, with `TMPDIR` pointing at a fresh directory, and whatever
+/// appears there is the spill. The child sees `POLARS_FORCE_OOC` and sorts in-process.
+fn benchmark_external_sort(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<BenchmarkResult> {
+    if std::env::var_os("POLARS_FORCE_OOC").is_some() {
+        let result = benchmark_sort(&df.clone().with_streaming(true), opts)?;
+        return Ok(BenchmarkResult { operation: "sort_external".to_string(), ..result });
+    }
+    
+    let spill_dir = TempDirGuard::new("polars-benchmark-spill")?;
+    let output_dir = TempDirGuard::new("polars-benchmark-external-sort")?;
+    let output = output_dir.0.join("external_sort.json");
+    let envs = [("TMPDIR", spill_dir.0.as_os_str()), ("POLARS_FORCE_OOC", OsStr::new("1"))];
+    let suite = run_child_suite(&["external_sort".to_string()], &envs, &output)?;
+    let result = suite
+        .results
+        .into_iter()
+        .find(|result| !result.streaming)
+        .ok_or_else(|| PolarsError::ComputeError("external sort child recorded no result".into()))?;
+    
+    let spill_bytes = dir_size_bytes(&spill_dir.0)?;
+    Ok(BenchmarkResult { spill_occurred: Some(spill_bytes > 0), spill_bytes: Some(spill_bytes), ..result })
+}
+
+/// Total size of the files under `dir`, recursively.
+fn dir_size_bytes(dir: &Path) -> PolarsResult<u64> {
+    let mut total = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        total += if metadata.is_dir() { dir_size_bytes(&entry.path())? } else { metadata.len() };
+    }
+    Ok(total)
+}
+
+/// Reads up to `--max-eager-rows` rows of the scan with its inferred schema and with a
+/// schema that narrows every `Float64` column to `Float32`. `ScanArgsParquet` has no schema field in Polars 0.32
+/// and Parquet doubles cannot be decoded as floats, so the override is applied as
+/// casts on the scan, which the optimizer runs as part of the read.
+fn benchmark_read_with_schema_override(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let inferred = df.schema()?;
+    let overridden: Schema = inferred
+        .iter()
+        .map(|(name, dtype)| {
+            let dtype = if *dtype == DataType::Float64 { DataType::Float32 } else { dtype.clone() };
+            Field::new(name, dtype)
+        })
+        .collect();
+    let casts: Vec<Expr> = overridden
+        .iter()
+        .filter(|(name, dtype)| inferred.get(name) != Some(*dtype))
+        .map(|(name, dtype)| col(name).cast(dtype.clone()))
+        .collect();
+    let columns_with_overridden_dtype = Some(casts.len());
+    
+    let (read, inferred_duration, memory_used) = measure(|| collect_capped(df, opts))?;
+    let inferred_result = BenchmarkResult {
+        output_size_bytes: Some(read.estimated_size() as u64),
+        ..BenchmarkResult::new("read_inferred_schema", inferred_duration, memory_used, Some(read.height()))
+    }
+    .with_output(&read, opts)?;
+    drop(read);
+    
+    let (read, duration, memory_used) = measure(|| collect_capped(&df.clone().with_columns(casts), opts))?;
+    let overridden_result = BenchmarkResult {
+        columns_with_overridden_dtype,
+        output_size_bytes: Some(read.estimated_size() as u64),
+        speedup_ratio: speedup(inferred_duration, duration),
+        ..BenchmarkResult::new("read_schema_override_float32", duration, memory_used, Some(read.height()))
+    }
+    .with_output(&read, opts)?;
+    
+    Ok(vec![inferred_result, overridden_result])
+}
+
+/// Deduplicates up to `--max-eager-rows` rows on `name` (26 values), on `name` and
+/// `id` together and on every column, each keeping the first and the last row of
+/// every duplicate set.
+fn benchmark_unique_subsets(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    let subsets: [(&str, Option<Vec<String>>); 3] = [
+        ("name", Some(vec!["name".to_string()])),
+        ("name_id", Some(vec!["name".to_string(), "id".to_string()])),
+        ("all_columns", None),
+    ];
+    
+    let mut results = Vec::new();
+    for (label, subset) in &subsets {
+        for (keep_label, keep) in [("first", UniqueKeepStrategy::First), ("last", UniqueKeepStrategy::Last)] {
+            let (unique, duration, memory_used) = measure(|| frame.unique(subset.as_deref(), keep, None))?;
+            let operation = format!("unique_{}_keep_{}", label, keep_label);
+            results.push(
+                BenchmarkResult {
+                    output_row_count: Some(unique.height()),
+                    duplicates_removed_fraction: (frame.height() > 0)
+                        .then(|| 1.0 - unique.height() as f64 / frame.height() as f64),
+                    ..BenchmarkResult::new(&operation, duration, memory_used, Some(frame.height()))
+                }
+                .with_output(&unique, opts)?,
+            );
+        }
+    }
+    
+    Ok(results)
+}
+
+/// Writes up to `--max-eager-rows` rows to CSV with the default options, a tab
+/// separator, an explicit datetime format, a leading UTF-8 BOM and every field
+/// quoted, recording each file's size. `CsvWriter` has no BOM option in Polars 0.32,
+/// so the BOM is written to the file before the CSV.
+fn benchmark_write_csv_options(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    struct CsvConfig {
+        label: &'static str,
+        separator: u8,
+        datetime_format: Option<&'static str>,
+        bom: bool,
+        quote_style: QuoteStyle,
+    }
+    let default = CsvConfig {
+        label: "default",
+        separator: b',',
+        datetime_format: None,
+        bom: false,
+        quote_style: QuoteStyle::Necessary,
+    };
+    let configs = [
+        CsvConfig { label: "tab_separator", separator: b'\t', ..default },
+        CsvConfig { label: "datetime_format", datetime_format: Some("%Y-%m-%d %H:%M:%S"), ..default },
+        CsvConfig { label: "bom", bom: true, ..default },
+        CsvConfig { label: "quote_all", quote_style: QuoteStyle::Always, ..default },
+    ];
+    
+    let mut frame = collect_capped(df, opts)?;
+    let dir = TempDirGuard::new("polars-benchmark-csv")?;
+    let mut results = Vec::new();
+    for config in std::iter::once(default).chain(configs) {
+        let path = dir.0.join(format!("{}.csv", config.label));
+        let (_, duration, memory_used) = measure(|| {
+            let mut file = std::fs::File::create(&path)?;
+            if config.bom {
+                std::io::Write::write_all(&mut file, b"\xEF\xBB\xBF")?;
+            }
+            CsvWriter::new(&mut file)
+                .with_delimiter(config.separator)
+                .with_datetime_format(config.datetime_format.map(str::to_string))
+                .with_quote_style(config.quote_style)
+                .finish(&mut frame)
+        })?;
+        let quoting = match config.quote_style {
+            QuoteStyle::Always => "always",
+            QuoteStyle::Necessary => "necessary",
+            QuoteStyle::NonNumeric => "non_numeric",
+        };
+        results.push(BenchmarkResult {
+            csv_separator: Some(config.separator as char),
+            csv_quoting: Some(quoting.to_string()),
+            file_size_bytes: Some(std::fs::metadata(&path)?.len()),
+            ..BenchmarkResult::new(&format!("write_csv_{}", config.label), duration, memory_used, Some(frame.height()))
+        });
+    }
+    
+    Ok(results)
+}
+
+/// Filters the scan on `x > 0`, `y < 0.5` and `id > 950` as one combined predicate
+/// and as three chained `filter` calls, timing `explain(true)` (planning only) and
+/// `collect` for each. The optimizer should merge the chained filters into one
+/// predicate, leaving planning as the only difference.
+fn benchmark_predicate_split(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let predicates = [col("x").gt(lit(0.0)), col("y").lt(lit(0.5)), col("id").gt(lit(950))];
+    let [a, b, c] = predicates.clone();
+    let queries = [
+        ("predicate_combined", df.clone().filter(a.and(b).and(c))),
+        ("predicate_chained", predicates.into_iter().fold(df.clone(), |lazy, predicate| lazy.filter(predicate))),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, query) in queries {
+        let (_, duration, memory_used) = measure(|| query.explain(true))?;
+        results.push(BenchmarkResult::new(&format!("{}_plan", operation), duration, memory_used, None));
+        
+        let (filtered, duration, memory_used) = measure(|| query.collect())?;
+        results.push(
+            BenchmarkResult::new(&format!("{}_collect", operation), duration, memory_used, Some(filtered.height()))
+                .with_output(&filtered, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Writes the complex query to Parquet once by collecting it and writing the frame,
+/// and once with `sink_parquet`, which streams batches to the file without holding
+/// the whole result, sampling peak memory for each. The single result's duration
+/// is the sink's, and `speedup_ratio` is relative to collect then write.
+fn benchmark_collect_vs_sink(df: &LazyFrame) -> PolarsResult<BenchmarkResult> {
+    let dir = TempDirGuard::new("polars-benchmark-sink")?;
+    
+    let sampler = PeakMemorySampler::start();
+    let (_, collect_duration, _) = measure(|| {
+        let mut collected = complex_query(df).collect()?;
+        ParquetWriter::new(std::fs::File::create(dir.0.join("collected.parquet"))?).finish(&mut collected)
+    })?;
+    let peak_memory_collect_mb = Some(sampler.stop());
+    
+    let sampler = PeakMemorySampler::start();
+    let sink_path = dir.0.join("sunk.parquet");
+    let (_, duration, memory_used) = measure(|| complex_query(df).sink_parquet(sink_path, ParquetWriteOptions::default()))?;
+    let peak_memory_sink_mb = Some(sampler.stop());
+    
+    Ok(BenchmarkResult {
+        peak_memory_collect_mb,
+        peak_memory_sink_mb,
+        speedup_ratio: speedup(collect_duration, duration),
+        ..BenchmarkResult::new("collect_vs_sink_parquet", duration, memory_used, None)
+    })
+}
+
+/// Computes the range (`max - min`) of `x` per `name` with a Rust closure passed to
+/// `GroupBy::apply`, which receives every group as its own frame, against the native
+/// `agg` expression. `speedup_ratio` on the native result is how much faster it was
+/// than `apply`.
+fn benchmark_groupby_apply(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name"), col("x")]), opts)?;
+    
+    let (applied, apply_duration, memory_used) = measure(|| {
+        frame.groupby_stable(["name"])?.apply(|group| {
+            let x = group.column("x")?;
+            let range = x.max::<f64>().zip(x.min::<f64>()).map(|(max, min)| max - min);
+            DataFrame::new(vec![group.column("name")?.head(Some(1)), Series::new("x_range", [range])])
+        })
+    })?;
+    let apply_result = BenchmarkResult::new("groupby_apply_range", apply_duration, memory_used, Some(applied.height()))
+        .with_output(&applied, opts)?;
+    
+    let (native, duration, memory_used) = measure(|| {
+        frame.clone()
+            .lazy()
+            .groupby_stable([col("name")])
+            .agg([(col("x").max() - col("x").min()).alias("x_range")])
+            .collect()
+    })?;
+    let native_result = BenchmarkResult {
+        speedup_ratio: speedup(apply_duration, duration),
+        ..BenchmarkResult::new("groupby_agg_range", duration, memory_used, Some(native.height()))
+    }
+    .with_output(&native, opts)?;
+    
+    Ok(vec![apply_result, native_result])
+}
+
+/// Extracts the last capture group of regexes with one, two and three groups from
+/// `name`, and every lowercase letter with `extract_all`, against `contains` with a
+/// comparable pattern as the baseline cost of compiling and matching a regex.
+fn benchmark_string_extract(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name")]), opts)?;
+    
+    let variants = vec![
+        ("string_contains_baseline".to_string(), col("name").str().contains(lit("[A-Z][a-z]+"), true)),
+        ("string_extract_1_group".to_string(), col("name").str().extract("([A-Z])", 1)),
+        ("string_extract_2_groups".to_string(), col("name").str().extract("([A-Z])([a-z]+)", 2)),
+        ("string_extract_3_groups".to_string(), col("name").str().extract("([A-Z])([a-z])([a-z]+)", 3)),
+    ];
+    let mut results = time_expressions(&frame, variants, opts)?;
+    
+    let (matches, duration, memory_used) = measure(|| {
+        frame.clone()
+            .lazy()
+            .select([col("name").str().extract_all(lit("[a-z]")).alias("matches")])
+            .collect()
+    })?;
+    let total_matches_found = matches.column("matches")?.list()?.lst_lengths().sum().map(|n| n as usize);
+    results.push(
+        BenchmarkResult {
+            total_matches_found,
+            ..BenchmarkResult::new("string_extract_all", duration, memory_used, Some(matches.height()))
+        }
+        .with_output(&matches, opts)?,
+    );
+    
+    Ok(results)
+}
+
+/// Ranks `x` with each tie-breaking method. `Ordinal` is close to an `arg_sort`,
+/// while `Average` also has to resolve ties into fractional ranks.
+fn benchmark_rank_methods(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?;
+    let methods = [
+        ("average", RankMethod::Average),
+        ("min", RankMethod::Min),
+        ("max", RankMethod::Max),
+        ("dense", RankMethod::Dense),
+        ("ordinal", RankMethod::Ordinal),
+    ];
+    
+    let variants: Vec<_> = methods
+        .into_iter()
+        .map(|(label, method)| (format!("rank_{}", label), col("x").rank(RankOptions { method, descending: false }, None)))
+        .collect();
+    time_expressions(&frame, variants, opts)
+}
+
+/// Writes up to `--max-eager-rows` rows to Parquet as they are and with `id` drifted
+/// to `Int32`, then reads the first file, and the drifted one while checking its
+/// schema against the original and casting every drifted column back. The gap
+/// between the reads is the cost of the check and the casts.
+fn benchmark_schema_mismatch(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut frame = collect_capped(df, opts)?;
+    let expected = frame.schema();
+    let dir = TempDirGuard::new("polars-benchmark-schema-drift")?;
+    
+    let matching = dir.0.join("matching.parquet");
+    ParquetWriter::new(std::fs::File::create(&matching)?).finish(&mut frame)?;
+    let drifted = dir.0.join("drifted.parquet");
+    let mut drifted_frame = frame.clone().lazy().with_columns([col("id").cast(DataType::Int32)]).collect()?;
+    ParquetWriter::new(std::fs::File::create(&drifted)?).finish(&mut drifted_frame)?;
+    
+    let (read, duration, memory_used) =
+        measure(|| LazyFrame::scan_parquet(&matching, ScanArgsParquet::default())?.collect())?;
+    let matching_result = BenchmarkResult::new("scan_matching_schema", duration, memory_used, Some(read.height()))
+        .with_output(&read, opts)?;
+    
+    let ((read, cast_columns), duration, memory_used) = measure(|| {
+        let scan = LazyFrame::scan_parquet(&drifted, ScanArgsParquet::default())?;
+        let casts: Vec<Expr> = scan
+            .schema()?
+            .iter()
+            .filter_map(|(name, dtype)| {
+                let expected_dtype = expected.get(name).filter(|expected_dtype| *expected_dtype != dtype)?;
+                Some(col(name).cast(expected_dtype.clone()))
+            })
+            .collect();
+        let cast_columns = casts.len();
+        Ok((scan.with_columns(casts).collect()?, cast_columns))
+    })?;
+    let drifted_result = BenchmarkResult {
+        schema_cast_columns: Some(cast_columns),
+        schema_mismatch_count: Some(schema_mismatches(&read.schema(), &expected)),
+        ..BenchmarkResult::new("scan_schema_drift_recovery", duration, memory_used, Some(read.height()))
+    }
+    .with_output(&read, opts)?;
+    
+    Ok(vec![matching_result, drifted_result])
+}
+
+/// Times `DataFrame::clone` on generated three-column frames of 100K, 1M and 10M
+/// rows, and turning a frame into a `LazyFrame` by moving it against cloning it first.
+/// Columns are reference-counted Arrow buffers, so a clone copies no data and should
+/// take the same time whatever `frame_memory_bytes` is.
+fn benchmark_clone() -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut results = Vec::new();
+    for len in [100_000, 1_000_000, 10_000_000] {
+        let frame = df!(
+            "id" => (0..len as i64).collect::<Vec<_>>(),
+            "x" => (0..len).map(|i| i as f64).collect::<Vec<_>>(),
+            "y" => (0..len).map(|i| -(i as f64)).collect::<Vec<_>>()
+        )?;
+        let frame_memory_bytes = Some(frame.estimated_size() as u64);
+        
+        let (cloned, duration, memory_used) = measure(|| Ok(frame.clone()))?;
+        results.push(BenchmarkResult {
+            frame_memory_bytes,
+            ..BenchmarkResult::new(&format!("dataframe_clone_{}", len), duration, memory_used, Some(cloned.height()))
+        });
+        
+        let owned = cloned;
+        let (_, duration, memory_used) = measure(|| Ok(owned.lazy()))?;
+        results.push(BenchmarkResult {
+            frame_memory_bytes,
+            ..BenchmarkResult::new(&format!("dataframe_lazy_{}", len), duration, memory_used, Some(len))
+        });
+        
+        let (_, duration, memory_used) = measure(|| Ok(frame.clone().lazy()))?;
+        results.push(BenchmarkResult {
+            frame_memory_bytes,
+            ..BenchmarkResult::new(&format!("dataframe_clone_lazy_{}", len), duration, memory_used, Some(len))
+        });
+    }
+    
+    Ok(results)
+}
+
+/// Sorts by `name`, which has 26 values and so many equal keys, with and without
+/// `maintain_order`. `speedup_ratio` on the unstable sort is relative to the stable
+/// one. Before timing, a four-row frame with two pairs of equal keys checks that the
+/// stable sort keeps input order.
+fn benchmark_sort_stability_comparison(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let sort_options = |maintain_order: bool| SortOptions { maintain_order, ..Default::default() };
+    
+    let ties = df!("x" => [1.0, 0.0, 1.0, 0.0], "tag" => ["a", "b", "c", "d"])?;
+    let sorted_tags: Vec<String> = ties
+        .lazy()
+        .sort("x", sort_options(true))
+        .collect()?
+        .column("tag")?
+        .utf8()?
+        .into_no_null_iter()
+        .map(str::to_string)
+        .collect();
+    if sorted_tags != ["b", "d", "a", "c"] {
+        return Err(PolarsError::ComputeError(
+            format!("stable sort reordered equal keys: expected [b, d, a, c], got {:?}", sorted_tags).into(),
+        ));
+    }
+    
+    let frame = collect_capped(df, opts)?;
+    let mut results = Vec::new();
+    let mut stable_duration = Duration::ZERO;
+    for (operation, maintain_order) in [("sort_stable", true), ("sort_unstable", false)] {
+        let (sorted, duration, memory_used) = measure(|| frame.clone().lazy().sort("name", sort_options(maintain_order)).collect())?;
+        let speedup_ratio = if maintain_order {
+            stable_duration = duration;
+            None
+        } else {
+            speedup(stable_duration, duration)
+        };
+        results.push(
+            BenchmarkResult {
+                is_stable: Some(maintain_order),
+                speedup_ratio,
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(sorted.height()))
+            }
+            .with_output(&sorted, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Builds a Series of 1M and 10M values one `append_value` at a time with a
+/// `PrimitiveChunkedBuilder` sized up front and one that starts empty and grows,
+/// against collecting the values into a `Vec` and calling `Series::new`.
+fn benchmark_series_builder() -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut results = Vec::new();
+    for len in [1_000_000, 10_000_000] {
+        for (suffix, capacity) in [("_preallocated", len), ("", 0)] {
+            let (series, duration, memory_used) = measure(|| {
+                let mut builder = PrimitiveChunkedBuilder::<Float64Type>::new("x", capacity);
+                for i in 0..len {
+                    builder.append_value(i as f64);
+                }
+                Ok(builder.finish().into_series())
+            })?;
+            results.push(BenchmarkResult {
+                capacity_pre_allocated: Some(capacity > 0),
+                ..BenchmarkResult::new(&format!("series_builder_{}{}", len, suffix), duration, memory_used, Some(series.len()))
+            });
+        }
+        
+        let (series, duration, memory_used) =
+            measure(|| Ok(Series::new("x", (0..len).map(|i| i as f64).collect::<Vec<_>>())))?;
+        let operation = format!("series_from_collected_vec_{}", len);
+        results.push(BenchmarkResult::new(&operation, duration, memory_used, Some(series.len())));
+    }
+    
+    Ok(results)
+}
+
+/// Formats `timestamp` as ISO 8601 strings, then times parsing them back into
+/// microsecond datetimes with `strptime`, with and without the cache of already
+/// converted values. Parsing is not strict, so unparseable strings become nulls and
+/// are counted as `parse_failures`.
+fn benchmark_datetime_parsing(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    const FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+    let frame = collect_capped(
+        &df.clone().select([col("timestamp").dt().strftime(FORMAT).alias("timestamp_str")]),
+        opts,
+    )?;
+    
+    let mut results = Vec::new();
+    for (operation, cache) in [("strptime_cached", true), ("strptime_uncached", false)] {
+        let options = StrptimeOptions {
+            format: Some(FORMAT.to_string()),
+            strict: false,
+            exact: true,
+            cache,
+            use_earliest: None,
+        };
+        let (parsed, duration, memory_used) = measure(|| {
+            frame.clone()
+                .lazy()
+                .select([col("timestamp_str").str().strptime(DataType::Datetime(TimeUnit::Microseconds, None), options)])
+                .collect()
+        })?;
+        results.push(
+            BenchmarkResult {
+                parse_failures: Some(parsed.column("timestamp_str")?.null_count()),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(parsed.height()))
+            }
+            .with_output(&parsed, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Writes up to `--max-eager-rows` rows to an in-memory Arrow IPC stream in record
+/// batches of 1024 rows, 65536 rows and one batch for everything. `IpcStreamWriter`
+/// has no batch size option in Polars 0.32 and writes one batch per chunk, so the
+/// frame is split into chunks of the batch size before timing starts.
+fn benchmark_write_ipc_stream(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    
+    let mut results = Vec::new();
+    for (label, batch_size) in [("1024", 1_024), ("65536", 65_536), ("single_batch", usize::MAX)] {
+        let mut batched = batched_frame(&frame, batch_size)?;
+        let (buffer, duration, memory_used) = measure(|| {
+            let mut buffer = Vec::new();
+            IpcStreamWriter::new(&mut buffer).finish(&mut batched)?;
+            Ok(buffer)
+        })?;
+        results.push(BenchmarkResult {
+            num_record_batches: Some(frame.height().div_ceil(batch_size)),
+            output_bytes: Some(buffer.len() as u64),
+            ..BenchmarkResult::new(&format!("write_ipc_stream_{}", label), duration, memory_used, Some(frame.height()))
+        });
+    }
+    
+    Ok(results)
+}
+
+/// Renames every column of 1000-row frames 10, 100 and 1000 columns wide with
+/// `DataFrame::rename` once per column, with one `set_column_names` call, and with a
+/// lazy `rename` that is then collected. Each method's `scaling_exponent` is fitted
+/// over the three widths: about 0 means the cost does not depend on the column count,
+/// 1 means it grows linearly with it.
+fn benchmark_column_rename(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    const ROWS: IdxSize = 1_000;
+    let methods = ["rename_loop", "set_column_names", "lazy_rename"];
+    
+    let mut results = Vec::new();
+    for width in [10, 100, 1_000] {
+        let old: Vec<String> = (0..width).map(|i| format!("col_{}", i)).collect();
+        let new: Vec<String> = (0..width).map(|i| format!("renamed_{}", i)).collect();
+        let copies: Vec<Expr> = old.iter().map(|name| col("x").alias(name)).collect();
+        let frame = df.clone().limit(ROWS).select(copies).collect()?;
+        
+        for method in methods {
+            let mut renamed = frame.clone();
+            let (_, duration, memory_used) = measure(|| {
+                match method {
+                    "rename_loop" => {
+                        for (old, new) in old.iter().zip(&new) {
+                            renamed.rename(old, new)?;
+                        }
+                    }
+                    "set_column_names" => renamed.set_column_names(&new)?,
+                    "lazy_rename" => renamed = frame.clone().lazy().rename(&old, &new).collect()?,
+                    _ => unreachable!("unknown rename method {}", method),
+                }
+                Ok(())
+            })?;
+            results.push(
+                BenchmarkResult {
+                    columns_in_frame: Some(width),
+                    ..BenchmarkResult::new(&format!("{}_{}", method, width), duration, memory_used, Some(renamed.height()))
+                }
+                .with_output(&renamed, opts)?,
+            );
+        }
+    }
+    
+    for method in methods {
+        let runs: Vec<&mut BenchmarkResult> = results
+            .iter_mut()
+            .filter(|result| result.operation.rsplit_once('_').is_some_and(|(prefix, _)| prefix == method))
+            .collect();
+        let points: Vec<(f64, f64)> = runs
+            .iter()
+            .map(|result| (result.columns_in_frame.unwrap_or(0) as f64, result.duration_us as f64))
+            .collect();
+        let exponent = scaling_exponent(&points);
+        for result in runs {
+            result.scaling_exponent = exponent;
+        }
+    }
+    
+    Ok(results)
+}
+
+/// Cross joins 10K rows of `x` with 10K rows of `y` and sums `x * y` over the joined
+/// rows, on the in-memory engine, which materializes the join first, and on the
+/// streaming engine, sampling peak memory for each. The engines add in a different
+/// order, so the float sums and checksums can differ in the last bits. Joins producing
+/// more than `--max-output-rows` rows are skipped.
+fn benchmark_cross_join_streaming(
+    df: &LazyFrame,
+    max_output_rows: usize,
+    opts: &BenchmarkOptions,
+) -> PolarsResult<Vec<BenchmarkResult>> {
+    const SIDE_ROWS: usize = 10_000;
+    let capped = BenchmarkOptions { max_eager_rows: opts.max_eager_rows.min(SIDE_ROWS), ..*opts };
+    let left = collect_capped(&df.clone().select([col("x")]), &capped)?;
+    let right = collect_capped(&df.clone().select([col("y")]), &capped)?;
+    let output_rows = left.height() * right.height();
+    if output_rows > max_output_rows {
+        eprintln!(
+            "⚠️  Skipping cross join: {} x {} rows would produce {} rows, above --max-output-rows {}",
+            left.height(),
+            right.height(),
+            output_rows,
+            max_output_rows
+        );
+        return Ok(Vec::new());
+    }
+    
+    let mut system = System::new_with_specifics(RefreshKind::new());
+    let mut results = Vec::new();
+    let mut growth_mb = Vec::new();
+    for (operation, streaming) in [("cross_join_in_memory", false), ("cross_join_streaming", true)] {
+        let query = left
+            .clone()
+            .lazy()
+            .cross_join(right.clone().lazy())
+            .select([(col("x") * col("y")).sum().alias("xy_sum")])
+            .with_streaming(streaming);
+        
+        let before_mb = process_rss_bytes(&mut system) / 1_024 / 1_024;
+        let sampler = PeakMemorySampler::start();
+        let (summed, duration, memory_used) = measure(|| query.collect())?;
+        let peak_memory_mb = sampler.stop();
+        growth_mb.push(peak_memory_mb.saturating_sub(before_mb));
+        results.push(
+            BenchmarkResult {
+                peak_memory_mb: Some(peak_memory_mb),
+                output_row_count: Some(output_rows),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(left.height() + right.height()))
+            }
+            .with_output(&summed, opts)?,
+        );
+    }
+    
+    if let [in_memory, streaming] = growth_mb[..] {
+        if in_memory > 2 * streaming.max(1) {
+            eprintln!(
+                "💡 The in-memory cross join grew memory by {} MB against {} MB streaming; \
+                 consider `with_streaming(true)` for large cross joins",
+                in_memory, streaming
+            );
+        }
+    }
+    
+    Ok(results)
+}
+
+/// Computes min, max, mean, std, null count and distinct count for every column, once
+/// as a single `select` holding all the expressions and once as one `select` per
+/// statistic. `column_statistics_individual` reports the summed time of the separate
+/// queries, and the fused result's `speedup_ratio` shows what sharing one pass saves.
+fn benchmark_column_statistics(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    let statistics: Vec<Expr> = frame
+        .get_column_names()
+        .into_iter()
+        .flat_map(|name| {
+            [
+                col(name).min().alias(&format!("{}_min", name)),
+                col(name).max().alias(&format!("{}_max", name)),
+                col(name).mean().alias(&format!("{}_mean", name)),
+                col(name).std(1).alias(&format!("{}_std", name)),
+                col(name).null_count().alias(&format!("{}_null_count", name)),
+                col(name).n_unique().alias(&format!("{}_n_unique", name)),
+            ]
+        })
+        .collect();
+    
+    let mut individual_duration = Duration::ZERO;
+    let mut individual_memory = 0;
+    for statistic in &statistics {
+        let (_, duration, memory_used) = measure(|| frame.clone().lazy().select([statistic.clone()]).collect())?;
+        individual_duration += duration;
+        individual_memory = individual_memory.max(memory_used);
+    }
+    let individual_result = BenchmarkResult::new(
+        "column_statistics_individual",
+        individual_duration,
+        individual_memory,
+        Some(frame.height()),
+    );
+    
+    let (fused, duration, memory_used) = measure(|| frame.clone().lazy().select(statistics.clone()).collect())?;
+    let fused_result = BenchmarkResult {
+        speedup_ratio: speedup(individual_duration, duration),
+        ..BenchmarkResult::new("column_statistics_fused", duration, memory_used, Some(frame.height()))
+    }
+    .with_output(&fused, opts)?;
+    
+    Ok(vec![individual_result, fused_result])
+}
+
+/// Sorts the elements inside each `x_list` list, ascending and descending, with
+/// `list().sort`, then builds the same sorted lists through `groupby_stable` with
+/// `x` sorted inside the aggregation. The group by path includes building the lists,
+/// which the list sort gets for free, so its time is the whole cost of that route.
+/// Both produce the same rows, and the checksums ignore row order, so they match.
+fn benchmark_list_sort(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name"), col("x")]), opts)?;
+    let (lists, total_list_elements) = list_column_frame(&frame.clone().lazy())?;
+    
+    let mut results = Vec::new();
+    for (order, descending) in [("ascending", false), ("descending", true)] {
+        let options = SortOptions { descending, ..Default::default() };
+        let (sorted, duration, memory_used) =
+            measure(|| lists.clone().lazy().with_columns([col("x_list").list().sort(options)]).collect())?;
+        results.push(
+            BenchmarkResult {
+                total_list_elements: Some(total_list_elements),
+                ..BenchmarkResult::new(&format!("list_sort_{}", order), duration, memory_used, Some(sorted.height()))
+            }
+            .with_output(&sorted, opts)?,
+        );
+        
+        let (grouped, duration, memory_used) = measure(|| {
+            frame
+                .clone()
+                .lazy()
+                .groupby_stable([col("name")])
+                .agg([col("x").sort(descending).alias("x_list")])
+                .collect()
+        })?;
+        let operation = format!("groupby_stable_sort_{}", order);
+        results.push(
+            BenchmarkResult {
+                total_list_elements: Some(total_list_elements),
+                ..BenchmarkResult::new(&operation, duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&grouped, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Computes the `name` group indices with an eager `groupby` and `get_groups`,
+/// without aggregating, then runs the same group by with a bare `count()`. The index
+/// result's `index_time_fraction` is the part of the group by spent finding groups,
+/// left out when the group by finished too fast to time.
+fn benchmark_arg_group_by(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name"), col("x")]), opts)?;
+    
+    let (groups, index_duration, memory_used) = measure(|| Ok(frame.groupby(["name"])?.get_groups().len()))?;
+    let (counted, duration, count_memory_used) =
+        measure(|| frame.clone().lazy().groupby([col("name")]).agg([count()]).collect())?;
+    
+    let index_result = BenchmarkResult {
+        result_rows: Some(groups),
+        index_time_fraction: speedup(index_duration, duration),
+        ..BenchmarkResult::new("arg_group_by", index_duration, memory_used, Some(frame.height()))
+    };
+    let count_result = BenchmarkResult {
+        result_rows: Some(counted.height()),
+        ..BenchmarkResult::new("group_by_count", duration, count_memory_used, Some(frame.height()))
+    }
+    .with_output(&counted, opts)?;
+    
+    Ok(vec![index_result, count_result])
+}
+
+/// Turns numbers into strings three ways: casting `id` to `Utf8`, formatting it as
+/// `ID_{}` with `format_str`, and rounding `x` to four decimals before casting.
+fn benchmark_format_integers(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("id"), col("x")]), opts)?;
+    
+    let conversions = [
+        ("format_cast_int", col("id").cast(DataType::Utf8)),
+        ("format_str_int", format_str("ID_{}", [col("id")])?),
+        ("format_cast_float_round_4", col("x").round(4).cast(DataType::Utf8)),
+    ];
+    time_selections(&frame, conversions, opts)
+}
+
+/// Replaces ten substrings of `name` with `_`, once as ten chained literal
+/// `replace_all` calls and once as a single regex alternation. The chain uses
+/// `replace_all` rather than first-match `replace`, since the alternation rewrites
+/// every occurrence too. The regex result's `speedup_ratio` is relative to the chain.
+fn benchmark_replace_many(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    // Each pattern starts with a consonant and ends with a vowel, so no two matches can
+    // overlap and the chain and the alternation rewrite the same spans.
+    const PATTERNS: [&str; 10] = ["ba", "be", "ca", "co", "da", "de", "la", "le", "ma", "ne"];
+    let frame = collect_capped(&df.clone().select([col("name")]), opts)?;
+    
+    let chained = PATTERNS
+        .iter()
+        .fold(col("name"), |expr, pattern| expr.str().replace_all(lit(*pattern), lit("_"), true));
+    let (replaced, chained_duration, memory_used) =
+        measure(|| frame.clone().lazy().with_columns([chained]).collect())?;
+    let chained_result =
+        BenchmarkResult::new("replace_chained_10", chained_duration, memory_used, Some(frame.height()))
+            .with_output(&replaced, opts)?;
+    
+    let alternation = format!("({})", PATTERNS.join("|"));
+    let (replaced, duration, memory_used) = measure(|| {
+        frame
+            .clone()
+            .lazy()
+            .with_columns([col("name").str().replace_all(lit(alternation.as_str()), lit("_"), false)])
+            .collect()
+    })?;
+    let regex_result = BenchmarkResult {
+        speedup_ratio: speedup(chained_duration, duration),
+        ..BenchmarkResult::new("replace_regex_alternation_10", duration, memory_used, Some(frame.height()))
+    }
+    .with_output(&replaced, opts)?;
+    
+    Ok(vec![chained_result, regex_result])
+}
+
+/// Runs a read, a filter, a group by and a sort over up to `--max-eager-rows` rows,
+/// and compares `estimated_size` of each output with how far the peak resident set size rose above its level before the
+/// query. The allocator keeps memory freed by earlier queries and hands it out again,
+/// so the RSS growth is a lower bound on what a query touched. Outputs estimated at
+/// less than half their RSS growth are reported, since sizing work by
+/// `estimated_size` would underestimate the memory it needs.
+fn benchmark_memory_estimate_accuracy(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let df = &capped_scan(df, opts);
+    let queries = [
+        ("memory_estimate_read", df.clone()),
+        ("memory_estimate_filter", df.clone().filter(col("x").gt(lit(0.0)))),
+        (
+            "memory_estimate_group_by",
+            df.clone().groupby([col("name")]).agg([col("x").sum(), col("y").mean()]),
+        ),
+        ("memory_estimate_sort", df.clone().sort("x", SortOptions::default())),
+    ];
+    
+    let mut system = System::new_with_specifics(RefreshKind::new());
+    let mut results = Vec::new();
+    for (operation, query) in queries {
+        let before_bytes = process_rss_bytes(&mut system);
+        let sampler = PeakMemorySampler::start();
+        let (output, duration, memory_used) = measure(|| query.collect())?;
+        let peak_bytes = sampler.stop_bytes();
+        
+        let estimated = output.estimated_size() as u64;
+        let actual = peak_bytes.saturating_sub(before_bytes);
+        let estimate_error_pct =
+            (actual > 0).then(|| (estimated as f64 - actual as f64).abs() / actual as f64 * 100.0);
+        if estimated < actual / 2 {
+            eprintln!(
+                "⚠️  {}: estimated_size is {} bytes but RSS grew by {} bytes, a potential OOM risk",
+                operation, estimated, actual
+            );
+        }
+        results.push(
+            BenchmarkResult {
+                peak_memory_mb: Some(peak_bytes / 1_024 / 1_024),
+                estimated_size_bytes: Some(estimated),
+                rss_delta_bytes: Some(actual),
+                estimate_error_pct,
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(output.height()))
+            }
+            .with_output(&output, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Groups by a synthetic key with 10, 100, 1000, 10000 and 100000 distinct values and
+/// sums `x` per group. `id` only takes 200 values in this dataset, so the key is the
+/// row number modulo the cardinality instead, and with fewer rows than the
+/// cardinality every row is its own group. Each result carries the exponent fitted
+/// between `num_groups` and duration, 0 when the group count does not matter at all.
+fn benchmark_groupby_cardinality_scaling(
+    df: &LazyFrame,
+    opts: &BenchmarkOptions,
+) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("x")]), opts)?.with_row_count("row_nr", None)?;
+    
+    let mut results = Vec::new();
+    for cardinality in [10u32, 100, 1_000, 10_000, 100_000] {
+        let (grouped, duration, memory_used) = measure(|| {
+            frame
+                .clone()
+                .lazy()
+                .groupby([(col("row_nr") % lit(cardinality)).alias("key")])
+                .agg([col("x").sum()])
+                .collect()
+        })?;
+        results.push(
+            BenchmarkResult {
+                num_groups: Some(grouped.height()),
+                ..BenchmarkResult::new(
+                    &format!("groupby_cardinality_{}", cardinality),
+                    duration,
+                    memory_used,
+                    Some(frame.height()),
+                )
+            }
+            .with_output(&grouped, opts)?,
+        );
+    }
+    
+    let points: Vec<(f64, f64)> = results
+        .iter()
+        .map(|result| (result.num_groups.unwrap_or(0) as f64, result.duration_us as f64))
+        .collect();
+    let exponent = scaling_exponent(&points);
+    for result in &mut results {
+        result.scaling_exponent = exponent;
+    }
+    
+    Ok(results)
+}
+
+/// Backslash-escapes ASCII punctuation so `text` matches literally inside a regex.
+fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_ascii_punctuation() {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Filters `name` with regex `contains` against the literal predicates that do the
+/// same job: an anchored `^prefix` against `starts_with`, `suffix$` against
+/// `ends_with`, and a regex alternation of three substrings against three OR-ed
+/// `contains_literal` calls, standing in for `contains_any`, which Polars 0.32 lacks.
+/// The patterns come from the first names in the data, so they match something
+/// whatever the dataset, and each pair keeps the same rows.
+fn benchmark_string_predicates(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name")]), opts)?;
+    let samples: Vec<String> = frame
+        .column("name")?
+        .unique_stable()?
+        .utf8()?
+        .into_iter()
+        .flatten()
+        .take(3)
+        .map(|name| name.chars().take(2).collect())
+        .collect();
+    let Some(first) = frame.column("name")?.utf8()?.into_iter().flatten().next() else {
+        return Ok(Vec::new());
+    };
+    let chars: Vec<char> = first.chars().collect();
+    let prefix: String = chars.iter().take(2).collect();
+    let suffix: String = chars[chars.len().saturating_sub(2)..].iter().collect();
+    
+    let alternation = samples.iter().map(|sample| regex_escape(sample)).collect::<Vec<_>>().join("|");
+    let literal_or = samples
+        .iter()
+        .map(|sample| col("name").str().contains_literal(lit(sample.as_str())))
+        .reduce(|any, matched| any.or(matched))
+        .unwrap_or_else(|| lit(false));
+    let predicates = [
+        ("string_contains_prefix_regex", col("name").str().contains(lit(format!("^{}", regex_escape(&prefix))), true)),
+        ("string_starts_with", col("name").str().starts_with(lit(prefix.as_str()))),
+        ("string_contains_suffix_regex", col("name").str().contains(lit(format!("{}$", regex_escape(&suffix))), true)),
+        ("string_ends_with", col("name").str().ends_with(lit(suffix.as_str()))),
+        ("string_contains_any_regex", col("name").str().contains(lit(alternation), true)),
+        ("string_contains_any_literal_or", literal_or),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, predicate) in predicates {
+        let (matched, duration, memory_used) = measure(|| frame.clone().lazy().filter(predicate).collect())?;
+        results.push(
+            BenchmarkResult {
+                match_rate: Some(matched.height() as f64 / frame.height().max(1) as f64),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&matched, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Runs the filter, group by, sort and complex queries with `profile` instead of
+/// `collect`, keeping the time each plan node took. Each timing frame is also written
+/// as `profile_<query>.csv` next to the results file at `output`.
+fn benchmark_profile_query(df: &LazyFrame, output: &Path, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let queries = [
+        ("filter", df.clone().filter(col("x").gt(lit(0.5)))),
+        ("group_by", df.clone().groupby([col("name")]).agg([col("x").sum(), col("y").mean()])),
+        ("sort", df.clone().sort("x", SortOptions::default())),
+        ("complex_query", complex_query(df)),
+    ];
+    
+    let mut results = Vec::new();
+    for (query_name, query) in queries {
+        let ((profiled, mut timings), duration, memory_used) = measure(|| query.profile())?;
+        let node_timings = timings
+            .column("node")?
+            .utf8()?
+            .into_iter()
+            .zip(timings.column("start")?.u64()?)
+            .zip(timings.column("end")?.u64()?)
+            .filter_map(|((node, start), end)| Some((node?.to_string(), end?.saturating_sub(start?))))
+            .collect();
+        CsvWriter::new(std::fs::File::create(output.with_file_name(format!("profile_{}.csv", query_name)))?)
+            .finish(&mut timings)?;
+        
+        results.push(
+            BenchmarkResult {
+                node_timings: Some(node_timings),
+                ..BenchmarkResult::new(&format!("profile_{}", query_name), duration, memory_used, Some(profiled.height()))
+            }
+            .with_output(&profiled, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Total length of the encoded min and max values in the column statistics of every
+/// row group in `metadata`.
+fn statistics_size_bytes(metadata: &polars::export::arrow::io::parquet::read::FileMetaData) -> u64 {
+    metadata
+        .row_groups
+        .iter()
+        .flat_map(|row_group| row_group.columns())
+        .filter_map(|column| column.metadata().statistics.as_ref())
+        .flat_map(|statistics| [&statistics.min, &statistics.max, &statistics.min_value, &statistics.max_value])
+        .flatten()
+        .map(|value| value.len() as u64)
+        .sum()
+}
+
+/// Writes up to `--max-eager-rows` rows to Parquet in 128 row groups, with and without
+/// column statistics, and times reading each footer. The gap between the two is what
+/// the statistics add to the footer. It then times deserializing the statistics of
+/// every column into arrow arrays, the step predicate pushdown needs before it can
+/// skip a row group. None of this touches a data page.
+fn benchmark_statistics_parsing(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    use polars::export::arrow::io::parquet::read::{infer_schema, read_metadata, statistics};
+    
+    let mut frame = collect_capped(df, opts)?;
+    let dir = TempDirGuard::new("polars-benchmark-statistics-parsing")?;
+    
+    let mut results = Vec::new();
+    let mut with_statistics = None;
+    for (label, write_statistics) in [("with_statistics", true), ("without_statistics", false)] {
+        let path = dir.0.join(format!("{}.parquet", label));
+        ParquetWriter::new(std::fs::File::create(&path)?)
+            .with_statistics(write_statistics)
+            .with_row_group_size(Some((frame.height() / 128).max(1)))
+            .finish(&mut frame)?;
+        
+        let (metadata, duration, memory_used) = measure(|| Ok(read_metadata(&mut std::fs::File::open(&path)?)?))?;
+        results.push(BenchmarkResult {
+            num_row_groups: Some(metadata.row_groups.len()),
+            statistics_size_bytes: Some(statistics_size_bytes(&metadata)),
+            file_size_bytes: Some(std::fs::metadata(&path)?.len()),
+            ..BenchmarkResult::new(&format!("read_footer_{}", label), duration, memory_used, Some(metadata.num_rows))
+        });
+        with_statistics.get_or_insert(metadata);
+    }
+    
+    let Some(metadata) = with_statistics else {
+        return Ok(results);
+    };
+    let (parsed, duration, memory_used) = measure(|| {
+        let schema = infer_schema(&metadata)?;
+        schema
+            .fields
+            .iter()
+            .map(|field| statistics::deserialize(field, &metadata.row_groups))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(PolarsError::from)
+    })?;
+    results.push(BenchmarkResult {
+        num_row_groups: Some(metadata.row_groups.len()),
+        statistics_size_bytes: Some(statistics_size_bytes(&metadata)),
+        columns_in_frame: Some(parsed.len()),
+        ..BenchmarkResult::new("deserialize_statistics", duration, memory_used, Some(metadata.num_rows))
+    });
+    
+    Ok(results)
+}
+
+/// Passes three closures of growing cost to `GroupBy::apply` over the `name` groups:
+/// summing `x` into one row, sorting the group by `x`, and left joining the group with
+/// a lookup frame of the distinct `id`s. `apply` in Polars 0.32 takes no output
+/// schema, so none is given. `overhead_per_group_us` spreads each run over its groups.
+fn benchmark_groupby_apply_closure(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(&df.clone().select([col("name"), col("id"), col("x")]), opts)?;
+    let lookup = frame
+        .clone()
+        .lazy()
+        .select([col("id").unique()])
+        .with_column((col("id") * lit(2)).alias("id_doubled"))
+        .collect()?;
+    
+    type Closure<'a> = Box<dyn Fn(DataFrame) -> PolarsResult<DataFrame> + Send + Sync + 'a>;
+    let closures: [(&str, Closure); 3] = [
+        (
+            "groupby_apply_scalar",
+            Box::new(|group| {
+                let x_sum = group.column("x")?.sum::<f64>();
+                DataFrame::new(vec![group.column("name")?.head(Some(1)), Series::new("x_sum", [x_sum])])
+            }),
+        ),
+        ("groupby_apply_sort", Box::new(|group| group.sort(["x"], false, false))),
+        ("groupby_apply_join", Box::new(|group| group.left_join(&lookup, ["id"], ["id"]))),
+    ];
+    
+    let num_groups = frame.column("name")?.n_unique()?;
+    let mut results = Vec::new();
+    for (operation, closure) in closures {
+        let (applied, duration, memory_used) = measure(|| frame.groupby_stable(["name"])?.apply(&closure))?;
+        results.push(
+            BenchmarkResult {
+                num_groups: Some(num_groups),
+                overhead_per_group_us: Some(duration.as_micros() as f64 / num_groups.max(1) as f64),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&applied, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Keeps the rows whose `name` is in a lookup frame with a semi join, and the rows
+/// whose `name` is not with an anti join, against `is_in` and its negation on the same
+/// names. The lookup holds half of the distinct names, at most 1000, so both sides
+/// keep some rows. Each join keeps the same rows as its filter, so the checksums and
+/// `result_rows` of a pair match.
+fn benchmark_semi_anti_join(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let frame = collect_capped(df, opts)?;
+    let names = frame.column("name")?.unique()?.sort(false);
+    let lookup_names = names.head(Some((names.len() / 2).clamp(1, 1_000)));
+    let lookup = DataFrame::new(vec![lookup_names.clone()])?;
+    
+    let join = |how: JoinType| frame.clone().lazy().join(lookup.clone().lazy(), [col("name")], [col("name")], how.into());
+    let membership = col("name").is_in(lit(lookup_names));
+    let queries = [
+        ("semi_join", join(JoinType::Semi)),
+        ("is_in_filter", frame.clone().lazy().filter(membership.clone())),
+        ("anti_join", join(JoinType::Anti)),
+        ("not_is_in_filter", frame.clone().lazy().filter(membership.not())),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, query) in queries {
+        let (kept, duration, memory_used) = measure(|| query.collect())?;
+        results.push(
+            BenchmarkResult {
+                result_rows: Some(kept.height()),
+                ..BenchmarkResult::new(operation, duration, memory_used, Some(frame.height()))
+            }
+            .with_output(&kept, opts)?,
+        );
+    }
+    
+    Ok(results)
+}
+
+/// Writes 100, 1000 and 10000 Parquet files of 10 rows each, reads every set back
+/// through a `*.parquet` glob, and reads the same rows from a single file. Files past
+/// the last available row, under `--limit-rows`, are written empty.
+/// `file_listing_overhead_ms` is how much longer the glob scan took than the single
+/// file, which covers listing the directory and opening and planning every file.
+/// Both reads return the same rows, so the checksums of a pair match.
+fn benchmark_glob_expansion_overhead(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    const ROWS_PER_FILE: usize = 10;
+    let dir = TempDirGuard::new("polars-benchmark-glob")?;
+    
+    let mut results = Vec::new();
+    for num_files in [100, 1_000, 10_000] {
+        let capped = BenchmarkOptions { max_eager_rows: opts.max_eager_rows.min(num_files * ROWS_PER_FILE), ..*opts };
+        let mut frame = collect_capped(df, &capped)?;
+        let files_dir = dir.0.join(format!("files_{}", num_files));
+        std::fs::create_dir(&files_dir)?;
+        for i in 0..num_files {
+            let mut part = frame.slice((i * ROWS_PER_FILE) as i64, ROWS_PER_FILE);
+            ParquetWriter::new(std::fs::File::create(files_dir.join(format!("part-{:05}.parquet", i)))?)
+                .finish(&mut part)?;
+        }
+        let single = dir.0.join(format!("single_{}.parquet", num_files));
+        ParquetWriter::new(std::fs::File::create(&single)?).finish(&mut frame)?;
+        
+        let files_matched_by_glob = std::fs::read_dir(&files_dir)?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().extension().is_some_and(|extension| extension == "parquet"))
+            .count();
+        let single_result = BenchmarkResult {
+            operation: format!("read_single_file_{}_rows", frame.height()),
+            ..benchmark_read(&utf8_path(&single)?, &ScanArgsParquet::default(), opts)?
+        };
+        let glob_result = benchmark_read(&utf8_path(&files_dir.join("*.parquet"))?, &ScanArgsParquet::default(), opts)?;
+        let overhead_us = glob_result.duration_us as f64 - single_result.duration_us as f64;
+        results.push(single_result);
+        results.push(BenchmarkResult {
+            operation: format!("read_glob_{}_files", num_files),
+            files_matched_by_glob: Some(files_matched_by_glob),
+            file_listing_overhead_ms: Some(overhead_us / 1_000.0),
+            ..glob_result
+        });
+    }
+    
+    Ok(results)
+}
+
+/// Writes up to `--max-eager-rows` rows to Parquet with row groups of 1024, 65536,
+/// 122880 and 1048576 rows, then reads each file back. Polars 0.32 writes 512 * 512
+/// rows per group when no size is given, so none of these is its own default. Row
+/// groups larger than the frame leave a single group, and the writer splits the
+/// frame into whole groups, so their actual size is a little over the target.
+fn benchmark_row_group_size(df: &LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    let mut frame = collect_capped(df, opts)?;
+    let dir = TempDirGuard::new("polars-benchmark-row-group-size")?;
+    
+    let mut results = Vec::new();
+    for row_group_size in [1_024, 65_536, 122_880, 1_048_576] {
+        let path = dir.0.join(format!("row_group_{}.parquet", row_group_size));
+        // The writer keeps the frame's chunks as row groups, and splits them further
+        // only once the frame holds more than one group, so start from a single chunk.
+        frame.as_single_chunk();
+        let (written_bytes, duration, memory_used) = measure(|| {
+            ParquetWriter::new(std::fs::File::create(&path)?)
+                .with_row_group_size(Some(row_group_size))
+                .finish(&mut frame)
+        })?;
+        let file_size_bytes = Some(written_bytes);
+        let num_row_groups = Some(
+            polars::export::arrow::io::parquet::read::read_metadata(&mut std::fs::File::open(&path)?)?
+                .row_groups
+                .len(),
+        );
+        results.push(BenchmarkResult {
+            row_group_size: Some(row_group_size),
+            num_row_groups,
+            file_size_bytes,
+            ..BenchmarkResult::new(
+                &format!("write_parquet_row_group_{}", row_group_size),
+                duration,
+                memory_used,
+                Some(frame.height()),
+            )
+        });
+        results.push(BenchmarkResult {
+            operation: format!("read_parquet_row_group_{}", row_group_size),
+            row_group_size: Some(row_group_size),
+            num_row_groups,
+            file_size_bytes,
+            ..benchmark_read(&utf8_path(&path)?, &ScanArgsParquet::default(), opts)?
+        });
+    }
+    
+    Ok(results)
+}
+
+/// Builds the same 100K-row frame of `id`, `x` and `name` from three column vectors
+/// with `DataFrame::new`, and from one `Row` of `AnyValue`s per row with
+/// `DataFrame::from_rows`, which has to infer the schema and push every value through
+/// a dynamically typed buffer. `n_chunks_output` shows whether the row path left the
+/// columns fragmented. The column result's `speedup_ratio` is how many times faster it
+/// was than the rows.
+fn benchmark_row_vs_column_construction(opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    use polars::frame::row::Row;
+    
+    const ROWS: usize = 100_000;
+    let names: Vec<String> = (0..26u8).map(|i| format!("name_{}", (b'a' + i) as char)).collect();
+    
+    let (columns, column_duration, memory_used) = measure(|| {
+        let ids: Vec<i64> = (0..ROWS as i64).collect();
+        let xs: Vec<f64> = (0..ROWS).map(|i| i as f64 / ROWS as f64).collect();
+        let name_values: Vec<&str> = (0..ROWS).map(|i| names[i % names.len()].as_str()).collect();
+        DataFrame::new(vec![Series::new("id", ids), Series::new("x", xs), Series::new("name", name_values)])
+    })?;
+    let mut column_result = BenchmarkResult {
+        n_chunks_output: Some(columns.n_chunks()),
+        ..BenchmarkResult::new("construct_column_oriented", column_duration, memory_used, Some(columns.height()))
+    };
+    
+    let (rows, duration, memory_used) = measure(|| {
+        let rows: Vec<Row> = (0..ROWS)
+            .map(|i| {
+                Row::new(vec![
+                    AnyValue::Int64(i as i64),
+                    AnyValue::Float64(i as f64 / ROWS as f64),
+                    AnyValue::Utf8(names[i % names.len()].as_str()),
+                ])
+            })
+            .collect();
+        let mut frame = DataFrame::from_rows(&rows)?;
+        frame.set_column_names(&["id", "x", "name"])?;
+        Ok(frame)
+    })?;
+    let row_result = BenchmarkResult {
+        n_chunks_output: Some(rows.n_chunks()),
+        ..BenchmarkResult::new("construct_row_oriented", duration, memory_used, Some(rows.height()))
+    };
+    column_result.speedup_ratio = speedup(duration, column_duration);
+    
+    Ok(vec![column_result.with_output(&columns, opts)?, row_result.with_output(&rows, opts)?])
+}
+
+/// Takes a second scan of the dataset as `right_df` and times an inner join on `id`,
+/// a left join on `name` and a cross join of the first 10,000 rows of each side.
+/// `id` and `name` repeat all over the dataset, so joining the raw frames would
+/// produce rows quadratic in the input; the right side of the key joins is instead
+/// aggregated to one row per key, like a dimension table. The cross join keeps one
+/// column per side, which still makes 100M rows. `rows_processed` is the output height.
+fn benchmark_join(df: &LazyFrame, right_df: LazyFrame, opts: &BenchmarkOptions) -> PolarsResult<Vec<BenchmarkResult>> {
+    const CROSS_ROWS: usize = 10_000;
+    let cross_rows = opts.row_limit.map_or(CROSS_ROWS, |limit| limit.min(CROSS_ROWS)) as IdxSize;
+    
+    let joins = [
+        (
+            "join_inner",
+            df.clone().join(
+                right_df.clone().groupby([col("id")]).agg([col("y").mean().alias("y_mean")]),
+                [col("id")],
+                [col("id")],
+                JoinType::Inner.into(),
+            ),
+        ),
+        (
+            "join_left",
+            df.clone().join(
+                right_df.clone().groupby([col("name")]).agg([col("x").max().alias("x_max")]),
+                [col("name")],
+                [col("name")],
+                JoinType::Left.into(),
+            ),
+        ),
+        (
+            "join_cross",
+            df.clone()
+                .select([col("x")])
+                .limit(cross_rows)
+                .cross_join(right_df.select([col("y").alias("y_right")]).limit(cross_rows)),
+        ),
+    ];
+    
+    let mut results = Vec::new();
+    for (operation, query) in joins {
+        let (joined, duration, memory_used) = measure(|| query.collect())?;
+        results.push(BenchmarkResult::new(operation, duration, memory_used, Some(joined.height())).with_output(&joined, opts)?);
+    }
+    
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn repeat_to_len_empty_input_stays_empty() {
+        assert_eq!(repeat_to_len("", 10), "");
+    }
+    
+    #[test]
+    fn repeat_to_len_zero_target_is_empty() {
+        assert_eq!(repeat_to_len("Alice", 0), "");
+    }
+    
+    #[test]
+    fn repeat_to_len_truncates_shorter_target() {
+        assert_eq!(repeat_to_len("Alice", 3), "Ali");
+    }
+    
+    #[test]
+    fn repeat_to_len_repeats_to_exact_length() {
+        assert_eq!(repeat_to_len("Bob", 8), "BobBobBo");
+    }
+    
+    #[test]
+    fn repeat_to_len_cuts_multibyte_at_char_boundary() {
+        // "é" is two bytes, so a 3 byte target can only hold one of them
+        assert_eq!(repeat_to_len("é", 3), "é");
+        assert_eq!(repeat_to_len("aé", 4), "aéa");
+    }
+    
+    #[test]
+    fn url_encode_escapes_reserved_and_multibyte() {
+        assert_eq!(url_encode("a-b_c.d~e"), "a-b_c.d~e");
+        assert_eq!(url_encode("a b/é"), "a%20b%2F%C3%A9");
+    }
+    
+    #[test]
+    fn url_decode_round_trips_and_rejects_bad_escapes() {
+        assert_eq!(url_decode(&url_encode("Zoë & co")).as_deref(), Some("Zoë & co"));
+        assert_eq!(url_decode("%2"), None);
+        assert_eq!(url_decode("%zz"), None);
+        assert_eq!(url_decode("%FF"), None);
+    }
+    
+    #[test]
+    fn schema_mismatches_counts_wrong_missing_and_extra_fields() {
+        let expected = Schema::from_iter([Field::new("x", DataType::Float32), Field::new("name", DataType::Utf8)]);
+        assert_eq!(schema_mismatches(&expected, &expected), 0);
+        
+        let actual = Schema::from_iter([Field::new("x", DataType::Float64), Field::new("id", DataType::Int64)]);
+        // `x` has the wrong dtype, `id` is unexpected and `name` is missing
+        assert_eq!(schema_mismatches(&actual, &expected), 3);
+    }
+    
+    #[test]
+    fn registry_runs_selected_benchmarks_in_registration_order() {
+        let mut registry = BenchmarkRegistry::new();
+        for name in ["b", "a", "c"] {
+            registry.register(name, move |_, _| Ok(vec![BenchmarkResult::new(name, Duration::ZERO, 0, None)]));
+        }
+        let opts = BenchmarkOptions { verify_output: false, max_eager_rows: 0, row_limit: None, streaming: false, warmup_runs: 0 };
+        let operations = |selected: &[&str]| {
+            let selected: Vec<String> = selected.iter().map(|name| name.to_string()).collect();
+            let results = registry.run(&LazyFrame::default(), &opts, &selected)?;
+            PolarsResult::Ok(results.into_iter().map(|result| result.operation).collect::<Vec<_>>())
+        };
+        
+        assert_eq!(operations(&[]).unwrap(), ["b", "a", "c"]);
+        assert_eq!(operations(&["c", "b"]).unwrap(), ["b", "c"]);
+        assert!(operations(&["missing"]).is_err());
+    }
+    
+    #[test]
+    fn registry_discards_warmup_runs() {
+        let calls = std::cell::Cell::new(0);
+        let mut registry = BenchmarkRegistry::new();
+        registry.register("filter", |_, _| {
+            calls.set(calls.get() + 1);
+            Ok(vec![BenchmarkResult::new("filter", Duration::from_millis(calls.get()), 0, None)])
+        });
+        let opts = BenchmarkOptions { verify_output: false, max_eager_rows: 0, row_limit: None, streaming: false, warmup_runs: 2 };
+        let results = registry.run(&LazyFrame::default(), &opts, &[]).unwrap();
+        
+        assert_eq!(calls.get(), 3);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].duration_ms, Some(3));
+    }
+    
+    #[test]
+    fn registry_records_streaming_failures_as_skipped() {
+        let calls = std::cell::Cell::new(0);
+        let mut registry = BenchmarkRegistry::new();
+        registry.register("sort", |_, _| {
+            calls.set(calls.get() + 1);
+            if calls.get() == 1 {
+                Ok(vec![BenchmarkResult::new("sort", Duration::from_millis(5), 0, None)])
+            } else {
+                Err(PolarsError::InvalidOperation("not supported in streaming".into()))
+            }
+        });
+        let opts = BenchmarkOptions { verify_output: false, max_eager_rows: 0, row_limit: None, streaming: true, warmup_runs: 0 };
+        let results = registry.run(&LazyFrame::default(), &opts, &[]).unwrap();
+        
+        assert_eq!(results.len(), 2);
+        assert!(!results[0].streaming && results[0].skipped_reason.is_none());
+        assert!(results[1].streaming);
+        assert_eq!(results[1].duration_ms, None);
+        assert!(results[1].skipped_reason.as_deref().unwrap().contains("not supported in streaming"));
+    }
+    
+    #[test]
+    fn scaling_exponent_fits_power_law() {
+        let linear = [(100.0, 10.0), (1_000.0, 100.0), (10_000.0, 1_000.0)];
+        let quadratic = [(100.0, 1.0), (1_000.0, 100.0), (10_000.0, 10_000.0)];
+        assert!((scaling_exponent(&linear).unwrap() - 1.0).abs() < 1e-9);
+        assert!((scaling_exponent(&quadratic).unwrap() - 2.0).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn scaling_exponent_needs_two_usable_points() {
+        assert_eq!(scaling_exponent(&[(100.0, 0.0), (1_000.0, 5.0)]), None);
+        assert_eq!(scaling_exponent(&[(100.0, 5.0), (100.0, 7.0)]), None);
+    }
+    
+    #[test]
+    fn kfold_split_holds_out_every_row_once() -> PolarsResult<()> {
+        let df = df!("id" => (0..23i64).collect::<Vec<_>>())?;
+        let folds = kfold_split(&df, 5, 42)?;
+        assert_eq!(folds.len(), 5);
+        
+        let mut held_out: Vec<i64> = Vec::new();
+        for (train, test) in &folds {
+            assert_eq!(train.height() + test.height(), 23);
+            assert!((4..=5).contains(&test.height()));
+            held_out.extend(test.column("id")?.i64()?.into_no_null_iter());
+        }
+        held_out.sort();
+        assert_eq!(held_out, (0..23).collect::<Vec<_>>());
+        Ok(())
+    }
+    
+    #[test]
+    fn estimated_row_width_counts_fixed_widths_and_measures_strings() {
+        let numeric = Schema::from_iter([
+            Field::new("id", DataType::Int64),
+            Field::new("x", DataType::Float32),
+            Field::new("timestamp", DataType::Datetime(TimeUnit::Microseconds, None)),
+        ]);
+        let read = BenchmarkResult {
+            output_size_bytes: Some(3_000),
+            ..BenchmarkResult::new("read_parquet", Duration::ZERO, 0, Some(100))
+        };
+        assert_eq!(estimated_row_width_bytes(&numeric, &read), 20);
+        
+        let with_strings = Schema::from_iter([Field::new("id", DataType::Int64), Field::new("name", DataType::Utf8)]);
+        assert_eq!(estimated_row_width_bytes(&with_strings, &read), 30);
+    }
+    
+    #[test]
+    fn unselected_results_cover_the_other_shared_benchmarks() {
+        assert!(unselected_results(&[], InputFormat::Parquet).is_empty());
+        
+        let skipped = unselected_results(&[BenchmarkKind::Filter, BenchmarkKind::GroupBy], InputFormat::Csv);
+        let operations: Vec<_> = skipped.iter().map(|result| result.operation.as_str()).collect();
+        assert_eq!(operations, ["read_csv", "aggregation", "sort", "complex_query"]);
+        assert!(skipped.iter().all(|result| result.duration_ms.is_none() && result.skipped_reason.is_some()));
+        let json = serde_json::to_value(&skipped[0]).unwrap();
+        assert_eq!(json["duration_ms"], serde_json::Value::Null);
+    }
+    
+    #[test]
+    fn summarize_iterations_reports_mean_and_sample_stddev() {
+        let run = |filter_us: u64, sort_us: u64| {
+            vec![
+                BenchmarkResult::new("filter", Duration::from_micros(filter_us), 0, None),
+                BenchmarkResult::new("sort", Duration::from_micros(sort_us), 0, None),
+            ]
+        };
+        let results = summarize_iterations(vec![run(1_000, 5_000), run(3_000, 5_000)]);
+        
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].duration_us, 2_000);
+        assert_eq!(results[0].duration_ms, Some(2));
+        assert!((results[0].duration_stddev_ms.unwrap() - 2.0_f64.sqrt()).abs() < 1e-9);
+        assert_eq!(results[1].duration_stddev_ms, Some(0.0));
+        assert_eq!(results[1].iterations, Some(2));
+    }
 }
\ No newline at end of file
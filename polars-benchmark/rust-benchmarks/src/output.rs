@@ -0,0 +1,133 @@
+//! Writers for the results file in each `--output-format`.
+
+use crate::{BenchmarkResult, BenchmarkSuite};
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+/// Writes a finished suite in one file format.
+pub trait ResultsFormatter {
+    fn write(&self, suite: &BenchmarkSuite, writer: &mut dyn Write) -> io::Result<()>;
+}
+
+/// The full suite as pretty-printed JSON, the only format `--regression-suite` and
+/// `--compare` can read back.
+pub struct JsonFormatter;
+
+impl ResultsFormatter for JsonFormatter {
+    fn write(&self, suite: &BenchmarkSuite, writer: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *writer, suite)?;
+        writeln!(writer)
+    }
+}
+
+/// One row per result with the columns most spreadsheets need.
+pub struct CsvFormatter;
+
+impl ResultsFormatter for CsvFormatter {
+    fn write(&self, suite: &BenchmarkSuite, writer: &mut dyn Write) -> io::Result<()> {
+        writeln!(writer, "operation,duration_ms,memory_mb,rows_processed,rows_per_sec")?;
+        for result in &suite.results {
+            let [duration_ms, rows_processed, rows_per_sec] = optional_cells(result);
+            writeln!(
+                writer,
+                "{},{},{},{},{}",
+                result.label(),
+                duration_ms,
+                result.memory_mb,
+                rows_processed,
+                rows_per_sec
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A GitHub Markdown table of the same columns as `CsvFormatter`, plus the speedup
+/// over Python of each operation found in the `--compare` file.
+pub struct MarkdownFormatter {
+    /// `speedup_factor` by operation, empty without `--compare`
+    pub speedups: HashMap<String, f64>,
+}
+
+impl ResultsFormatter for MarkdownFormatter {
+    fn write(&self, suite: &BenchmarkSuite, writer: &mut dyn Write) -> io::Result<()> {
+        writeln!(writer, "| operation | duration_ms | memory_mb | rows_processed | rows_per_sec | speedup_vs_python |")?;
+        writeln!(writer, "|---|---:|---:|---:|---:|---:|")?;
+        for result in &suite.results {
+            let [duration_ms, rows_processed, rows_per_sec] = optional_cells(result);
+            let speedup = match self.speedups.get(&result.operation) {
+                Some(speedup) if !result.streaming => format!("{:.2}×", speedup),
+                _ => "N/A".to_string(),
+            };
+            writeln!(
+                writer,
+                "| {} | {} | {} | {} | {} | {} |",
+                result.label(),
+                duration_ms,
+                result.memory_mb,
+                rows_processed,
+                rows_per_sec,
+                speedup
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// `duration_ms`, `rows_processed` and `rows_per_second`, empty when the result has none.
+fn optional_cells(result: &BenchmarkResult) -> [String; 3] {
+    [
+        result.duration_ms.map(|ms| ms.to_string()).unwrap_or_default(),
+        result.rows_processed.map(|rows| rows.to_string()).unwrap_or_default(),
+        result.rows_per_second.map(|rows| format!("{:.0}", rows)).unwrap_or_default(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn suite() -> BenchmarkSuite {
+        serde_json::from_value(serde_json::json!({
+            "timestamp": "",
+            "results": [
+                {"operation": "filter", "duration_ms": 12, "memory_mb": 3, "rows_processed": 500, "rows_per_second": 41666.7},
+                {"operation": "filter", "duration_ms": 10, "memory_mb": 1, "rows_processed": null, "streaming": true},
+                {"operation": "sort", "duration_ms": 40, "memory_mb": 0, "rows_processed": 1000},
+                {"operation": "read_parquet", "duration_ms": null, "memory_mb": 0, "rows_processed": null, "skipped_reason": "not selected with --select"},
+            ],
+            "system_info": {"os": "", "cpu_count": 1, "total_memory_gb": 1},
+            "dataset_info": {"source": "", "rows_limit": null},
+        }))
+        .unwrap()
+    }
+    
+    fn render(formatter: &dyn ResultsFormatter) -> String {
+        let mut buffer = Vec::new();
+        formatter.write(&suite(), &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+    
+    #[test]
+    fn csv_has_one_row_per_result() {
+        assert_eq!(
+            render(&CsvFormatter),
+            "operation,duration_ms,memory_mb,rows_processed,rows_per_sec\n\
+             filter,12,3,500,41667\n\
+             filter (streaming),10,1,,\n\
+             sort,40,0,1000,\n\
+             read_parquet,,0,,\n"
+        );
+    }
+    
+    #[test]
+    fn markdown_fills_speedups_from_the_comparison() {
+        let formatter = MarkdownFormatter { speedups: HashMap::from([("filter".to_string(), 3.2)]) };
+        let table = render(&formatter);
+        let rows: Vec<&str> = table.lines().skip(2).collect();
+        assert_eq!(rows[0], "| filter | 12 | 3 | 500 | 41667 | 3.20× |");
+        assert_eq!(rows[1], "| filter (streaming) | 10 | 1 |  |  | N/A |");
+        assert_eq!(rows[2], "| sort | 40 | 0 | 1000 |  | N/A |");
+        assert_eq!(rows[3], "| read_parquet |  | 0 |  |  | N/A |");
+    }
+}
\ No newline at end of file
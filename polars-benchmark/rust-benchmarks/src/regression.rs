@@ -0,0 +1,129 @@
+//! Compares a benchmark run against a baseline results file for `--regression-suite`.
+
+use crate::BenchmarkSuite;
+use serde::Serialize;
+use std::collections::HashMap;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// An operation that got slower than the baseline by more than the threshold.
+#[derive(Serialize, Debug)]
+pub struct Regression {
+    pub operation: String,
+    pub baseline_ms: f64,
+    pub current_ms: f64,
+    /// `(current_ms - baseline_ms) / baseline_ms`, so 0.25 is 25% slower
+    pub relative_change: f64,
+}
+
+/// Contents of `regression_report.json`.
+#[derive(Serialize, Debug)]
+pub struct RegressionReport<'a> {
+    pub baseline: &'a str,
+    pub threshold: f64,
+    pub regressions: &'a [Regression],
+}
+
+/// Pairs each current result with the baseline result of the same operation on the
+/// same engine, in the current run's order. Operations missing from either side are
+/// left out, as are ones the baseline timed at zero, which have no relative change,
+/// and ones skipped in the current run. Streaming results are labelled as such.
+fn matched_durations(baseline: &BenchmarkSuite, current: &BenchmarkSuite) -> Vec<(String, f64, f64)> {
+    let baseline_ms: HashMap<(&str, bool), f64> =
+        baseline.results.iter().map(|result| (result.key(), result.precise_duration_ms())).collect();
+    current
+        .results
+        .iter()
+        .filter(|result| result.skipped_reason.is_none())
+        .filter_map(|result| {
+            let baseline_ms = *baseline_ms.get(&result.key())?;
+            (baseline_ms > 0.0).then(|| (result.label(), baseline_ms, result.precise_duration_ms()))
+        })
+        .collect()
+}
+
+/// Operations in `current` that are more than `threshold` slower, relatively, than the
+/// same operation in `baseline`.
+pub fn compare_suites(baseline: &BenchmarkSuite, current: &BenchmarkSuite, threshold: f64) -> Vec<Regression> {
+    matched_durations(baseline, current)
+        .into_iter()
+        .map(|(operation, baseline_ms, current_ms)| Regression {
+            operation,
+            baseline_ms,
+            current_ms,
+            relative_change: (current_ms - baseline_ms) / baseline_ms,
+        })
+        .filter(|regression| regression.relative_change > threshold)
+        .collect()
+}
+
+/// Prints every operation found in both runs to stderr, red when it is slower than the
+/// baseline by more than `threshold` and green when it is faster by more than that.
+pub fn print_diff_table(baseline: &BenchmarkSuite, current: &BenchmarkSuite, threshold: f64) {
+    let matched = matched_durations(baseline, current);
+    let width = matched.iter().map(|(operation, _, _)| operation.len()).max().unwrap_or(0).max("operation".len());
+    eprintln!("{:<width$}  {:>12}  {:>12}  {:>8}", "operation", "baseline_ms", "current_ms", "change");
+    for (operation, baseline_ms, current_ms) in matched {
+        let change = (current_ms - baseline_ms) / baseline_ms;
+        let color = if change > threshold {
+            RED
+        } else if change < -threshold {
+            GREEN
+        } else {
+            ""
+        };
+        let reset = if color.is_empty() { "" } else { RESET };
+        eprintln!(
+            "{}{:<width$}  {:>12.3}  {:>12.3}  {:>+7.1}%{}",
+            color,
+            operation,
+            baseline_ms,
+            current_ms,
+            change * 100.0,
+            reset
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn suite(durations_us: &[(&str, u64)]) -> BenchmarkSuite {
+        let results: Vec<_> = durations_us
+            .iter()
+            .map(|(operation, us)| {
+                serde_json::json!({"operation": operation, "duration_ms": us / 1_000, "duration_us": us, "memory_mb": 0})
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "timestamp": "",
+            "results": results,
+            "system_info": {"os": "", "cpu_count": 1, "total_memory_gb": 1},
+            "dataset_info": {"source": "", "rows_limit": null},
+        }))
+        .unwrap()
+    }
+    
+    #[test]
+    fn compare_suites_flags_only_slowdowns_past_threshold() {
+        let baseline = suite(&[("filter", 100_000), ("sort", 100_000), ("group_by", 100_000), ("zero", 0)]);
+        let current = suite(&[("filter", 125_000), ("sort", 105_000), ("group_by", 50_000), ("zero", 10), ("new", 1)]);
+        
+        let regressions = compare_suites(&baseline, &current, 0.1);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].operation, "filter");
+        assert!((regressions[0].relative_change - 0.25).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn compare_suites_falls_back_to_whole_milliseconds() {
+        let mut baseline = suite(&[("filter", 100_000)]);
+        baseline.results[0].duration_us = 0;
+        let regressions = compare_suites(&baseline, &suite(&[("filter", 150_500)]), 0.1);
+        assert_eq!(regressions[0].baseline_ms, 100.0);
+        assert_eq!(regressions[0].current_ms, 150.5);
+    }
+}
\ No newline at end of file
@@ -46,8 +46,13 @@ def compare_results():
     table.add_column("Python RAM (MB)", style="green")
     table.add_column("Rust RAM (MB)", style="red")
     
-    # Create lookup for rust results
-    rust_lookup = {result["operation"]: result for result in rust_data["results"]}
+    # Create lookup for rust results, leaving out `--streaming` reruns, skipped
+    # operations and ones killed by `--timeout-secs`, whose duration is only the timeout
+    rust_lookup = {
+        result["operation"]: result
+        for result in rust_data["results"]
+        if not result.get("streaming") and not result.get("timed_out") and "skipped_reason" not in result
+    }
     
     for py_result in python_data["results"]:
         operation = py_result["operation"]
@@ -69,19 +74,24 @@ def compare_results():
     
     console.print(table)
     
-    # Summary statistics
+    # Summary statistics, restricted to operations both suites ran so that
+    # Rust-only benchmarks don't skew the totals
     console.print("\n📈 [bold]Summary Statistics:[/bold]")
     
-    py_total = sum(r["duration_ms"] for r in python_data["results"])
-    rust_total = sum(r["duration_ms"] for r in rust_data["results"])
+    common_operations = [r for r in python_data["results"] if r["operation"] in rust_lookup]
+    rust_common = [rust_lookup[r["operation"]] for r in common_operations]
+    
+    py_total = sum(r["duration_ms"] for r in common_operations)
+    rust_total = sum(r["duration_ms"] for r in rust_common)
     overall_speedup = py_total / rust_total if rust_total > 0 else 0
     
+    console.print(f"  • Operations compared: {len(common_operations)}")
     console.print(f"  • Total Python time: {py_total}ms")
     console.print(f"  • Total Rust time: {rust_total}ms")
     console.print(f"  • Overall speedup: {overall_speedup:.2f}x")
     
-    py_memory = sum(r["memory_mb"] for r in python_data["results"])
-    rust_memory = sum(r["memory_mb"] for r in rust_data["results"])
+    py_memory = sum(r["memory_mb"] for r in common_operations)
+    rust_memory = sum(r["memory_mb"] for r in rust_common)
     
     console.print(f"  • Total Python memory: {py_memory}MB")
     console.print(f"  • Total Rust memory: {rust_memory}MB")